@@ -0,0 +1,118 @@
+use std::fmt;
+
+/// Answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i128),
+    Text(String),
+    Screen(Screen),
+}
+
+/// Monochrome pixel screen, such as the CRT of day 10.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Screen {
+    width: usize,
+    pixels: Vec<bool>,
+}
+
+impl Screen {
+    /// Creates a screen from lit (`true`) and dark pixels in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is zero.
+    #[must_use]
+    pub fn new(width: usize, pixels: Vec<bool>) -> Screen {
+        assert!(width > 0, "Screen width must be positive");
+        Screen { width, pixels }
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn pixels(&self) -> &[bool] {
+        &self.pixels
+    }
+
+    /// Renders each row as a string of `#` (lit) and `.` (dark).
+    pub fn rows(&self) -> impl Iterator<Item = String> + '_ {
+        self.pixels
+            .chunks(self.width)
+            .map(|row| row.iter().map(|&lit| if lit { '#' } else { '.' }).collect())
+    }
+}
+
+impl fmt::Display for Screen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{row}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Screen(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Int(i128::try_from(n).expect("integer answers fit in i128"))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(u32, u64, usize, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl From<Screen> for Answer {
+    fn from(s: Screen) -> Answer {
+        Answer::Screen(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn screen_rows() {
+        let screen = Screen::new(3, vec![true, false, true, false, true, false]);
+        assert_eq!(screen.rows().collect::<Vec<_>>(), ["#.#", ".#."]);
+        assert_eq!(Answer::from(screen).to_string(), "#.#\n.#.");
+    }
+
+    #[test]
+    fn int_answer() {
+        assert_eq!(Answer::from(-3_isize).to_string(), "-3");
+        assert_eq!(Answer::from(usize::MAX), Answer::Int(i128::from(u64::MAX)));
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{Answer, Part, Result};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Expected answer for one part of a puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    /// File stem of the input inside the day's directory, e.g. `sample`.
+    pub input: String,
+    pub part: Part,
+    pub answer: String,
+}
+
+impl Expected {
+    /// Returns whether `answer` matches the expected one.
+    #[must_use]
+    pub fn matches(&self, answer: &Answer) -> bool {
+        answer.to_string() == self.answer
+    }
+}
+
+/// Parses an answers file.
+///
+/// Each entry is a line `<input> <part>: <answer>`. Multi-line answers leave
+/// the value empty and continue on the following lines, indented by two
+/// spaces. Blank lines and lines starting with `#` are ignored.
+///
+/// # Errors
+///
+/// Fails on lines that are neither an entry nor a continuation.
+pub fn parse_answers(text: &str) -> Result<Vec<Expected>> {
+    let mut entries: Vec<Expected> = vec![];
+
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(row) = line.strip_prefix("  ") {
+            let last = entries
+                .last_mut()
+                .ok_or(format!("line {}: continuation without an entry", i + 1))?;
+            if !last.answer.is_empty() {
+                last.answer.push('\n');
+            }
+            last.answer.push_str(row);
+            continue;
+        }
+
+        let (key, answer) = line.split_once(':').ok_or(format!(
+            "line {}: expected `<input> <part>: <answer>`",
+            i + 1
+        ))?;
+        let (input, part) = key
+            .split_once(' ')
+            .ok_or(format!("line {}: expected `<input> <part>`", i + 1))?;
+        entries.push(Expected {
+            input: input.to_string(),
+            part: part.parse().map_err(|e| format!("line {}: {e}", i + 1))?,
+            answer: answer.trim().to_string(),
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Formats entries in the format read by [`parse_answers`].
+#[must_use]
+pub fn format_answers(entries: &[Expected]) -> String {
+    let mut text = String::new();
+    for entry in entries {
+        let part = entry.part.to_string().to_lowercase();
+        if entry.answer.contains('\n') {
+            writeln!(text, "{} {part}:", entry.input).unwrap();
+            for row in entry.answer.lines() {
+                writeln!(text, "  {row}").unwrap();
+            }
+        } else {
+            writeln!(text, "{} {part}: {}", entry.input, entry.answer).unwrap();
+        }
+    }
+    text
+}
+
+/// Loads `answers.txt` from a day's directory.
+///
+/// # Errors
+///
+/// Fails if the file can't be read or parsed.
+pub fn load_answers(day_dir: &Path) -> Result<Vec<Expected>> {
+    let path = day_dir.join("answers.txt");
+    parse_answers(&crate::read_input(&path)?).map_err(|e| format!("{}: {e}", path.display()).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Screen;
+
+    const ANSWERS: &str = "# comment
+sample a: 13140
+sample b:
+  #.
+  .#
+";
+
+    #[test]
+    fn parse() {
+        let entries = parse_answers(ANSWERS).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].input, "sample");
+        assert_eq!(entries[0].part, Part::A);
+        assert!(entries[0].matches(&Answer::Int(13140)));
+        assert!(entries[1].matches(&Screen::new(2, vec![true, false, false, true]).into()));
+    }
+
+    #[test]
+    fn roundtrip() {
+        let entries = parse_answers(ANSWERS).unwrap();
+        assert_eq!(parse_answers(&format_answers(&entries)).unwrap(), entries);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(parse_answers("  orphan").is_err());
+        assert!(parse_answers("sample c: 1").is_err());
+        assert!(parse_answers("sample a 1").is_err());
+    }
+}
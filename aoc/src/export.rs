@@ -0,0 +1,330 @@
+//! Still images and animations of grid-like puzzle state.
+//!
+//! Pictures are [`Grid`]s of [`Rgb`] colours with one cell per puzzle cell,
+//! scaled up to blocks of pixels when written. Days turn their state into
+//! pictures with their own colour mapping.
+
+use crate::geom::Point;
+use crate::grid::Grid;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// A colour with 8 bits per channel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb::new(0, 0, 0);
+    pub const WHITE: Rgb = Rgb::new(255, 255, 255);
+
+    #[must_use]
+    pub const fn new(r: u8, g: u8, b: u8) -> Rgb {
+        Rgb { r, g, b }
+    }
+
+    /// Mixes the colour with `other`, going from `self` at 0 to `other` at
+    /// `amount` 1.
+    #[must_use]
+    pub fn mix(self, other: Rgb, amount: f64) -> Rgb {
+        let amount = amount.clamp(0.0, 1.0);
+        let channel = |a: u8, b: u8| {
+            let mixed = f64::from(a) + (f64::from(b) - f64::from(a)) * amount;
+            // Stays within 0..=255 as it lies between `a` and `b`.
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let mixed = mixed.round() as u8;
+            mixed
+        };
+        Rgb::new(
+            channel(self.r, other.r),
+            channel(self.g, other.g),
+            channel(self.b, other.b),
+        )
+    }
+}
+
+/// Image file formats, chosen by the extension of the output path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ppm,
+    Png,
+    Gif,
+}
+
+impl Format {
+    /// Picks the format matching the extension of `path`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the extension is missing or not `ppm`, `png` or `gif`.
+    pub fn from_path(path: &Path) -> Result<Format, String> {
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        match ext.to_ascii_lowercase().as_str() {
+            "ppm" => Ok(Format::Ppm),
+            "png" => Ok(Format::Png),
+            "gif" => Ok(Format::Gif),
+            _ => Err(format!(
+                "Unknown image format of {}, expected .ppm, .png or .gif",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// Returns the pixels of `image` as RGB bytes, row by row, with every cell
+/// blown up to `scale` by `scale` pixels.
+fn pixels(image: &Grid<Rgb>, scale: usize) -> io::Result<Vec<u8>> {
+    let width = dimension::<usize>(image.width(), scale)?;
+    let height = dimension::<usize>(image.height(), scale)?;
+    let len = width
+        .checked_mul(height)
+        .and_then(|n| n.checked_mul(3))
+        .ok_or_else(|| io::Error::other(format!("image too large: {width}x{height} pixels")))?;
+    let mut data = Vec::with_capacity(len);
+    for row in image.rows() {
+        for _ in 0..scale {
+            for c in row {
+                for _ in 0..scale {
+                    data.extend([c.r, c.g, c.b]);
+                }
+            }
+        }
+    }
+    Ok(data)
+}
+
+fn dimension<T: TryFrom<usize>>(cells: usize, scale: usize) -> io::Result<T> {
+    cells
+        .checked_mul(scale)
+        .and_then(|n| T::try_from(n).ok())
+        .ok_or_else(|| io::Error::other(format!("image too large: {cells} cells at scale {scale}")))
+}
+
+/// Writes `image` as a binary PPM with every cell `scale` pixels wide.
+///
+/// # Errors
+///
+/// Fails if `out` can't be written to or the image is too large.
+pub fn write_ppm(image: &Grid<Rgb>, scale: usize, mut out: impl Write) -> io::Result<()> {
+    let width = dimension::<usize>(image.width(), scale)?;
+    let height = dimension::<usize>(image.height(), scale)?;
+    let data = pixels(image, scale)?;
+    write!(out, "P6\n{width} {height}\n255\n")?;
+    out.write_all(&data)?;
+    out.flush()
+}
+
+/// Writes `image` as a PNG with every cell `scale` pixels wide.
+///
+/// # Errors
+///
+/// Fails if `out` can't be written to or the image is too large.
+pub fn write_png(image: &Grid<Rgb>, scale: usize, out: impl Write) -> io::Result<()> {
+    let mut encoder = png::Encoder::new(
+        out,
+        dimension(image.width(), scale)?,
+        dimension(image.height(), scale)?,
+    );
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer
+        .write_image_data(&pixels(image, scale)?)
+        .map_err(io::Error::other)?;
+    writer.finish().map_err(io::Error::other)
+}
+
+/// Writes an animated GIF one frame at a time, so long animations don't
+/// have to be kept in memory.
+pub struct GifWriter<W: Write> {
+    encoder: gif::Encoder<W>,
+    width: u16,
+    height: u16,
+    scale: usize,
+    /// Delay between frames, in hundredths of a second.
+    delay: u16,
+}
+
+impl<W: Write> GifWriter<W> {
+    /// Starts an endlessly looping animation of frames with `width` by
+    /// `height` cells, each `scale` pixels wide, shown for `delay` each.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `out` can't be written to or the frames are too large.
+    pub fn new(
+        out: W,
+        width: usize,
+        height: usize,
+        scale: usize,
+        delay: Duration,
+    ) -> io::Result<GifWriter<W>> {
+        let (width, height) = (dimension(width, scale)?, dimension(height, scale)?);
+        let mut encoder = gif::Encoder::new(out, width, height, &[]).map_err(io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+        let delay = u16::try_from(delay.as_millis() / 10).unwrap_or(u16::MAX);
+        Ok(GifWriter {
+            encoder,
+            width,
+            height,
+            scale,
+            delay,
+        })
+    }
+
+    /// Appends `image` to the animation. Frames with up to 256 colours are
+    /// stored exactly, others are quantized.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `out` can't be written to or `image` doesn't have the size
+    /// the animation was started with.
+    pub fn write_frame(&mut self, image: &Grid<Rgb>) -> io::Result<()> {
+        let size = (
+            dimension(image.width(), self.scale)?,
+            dimension(image.height(), self.scale)?,
+        );
+        if size != (self.width, self.height) {
+            return Err(io::Error::other(format!(
+                "frame of {}x{} pixels in a {}x{} animation",
+                size.0, size.1, self.width, self.height
+            )));
+        }
+        let mut frame =
+            gif::Frame::from_rgb_speed(self.width, self.height, &pixels(image, self.scale)?, 10);
+        frame.delay = self.delay;
+        self.encoder.write_frame(&frame).map_err(io::Error::other)
+    }
+}
+
+/// Saves `image` to `path` in the format its extension asks for, a GIF
+/// being a single frame.
+///
+/// # Errors
+///
+/// Fails if the format is unknown or the file can't be written.
+pub fn save(image: &Grid<Rgb>, path: &Path, scale: usize) -> crate::Result<()> {
+    let format = Format::from_path(path)?;
+    let file =
+        File::create(path).map_err(|e| format!("Failed to create {}: {e}", path.display()))?;
+    let out = BufWriter::new(file);
+    let written = match format {
+        Format::Ppm => write_ppm(image, scale, out),
+        Format::Png => write_png(image, scale, out),
+        Format::Gif => GifWriter::new(out, image.width(), image.height(), scale, Duration::ZERO)
+            .and_then(|mut gif| gif.write_frame(image)),
+    };
+    written.map_err(|e| format!("Failed to write {}: {e}", path.display()).into())
+}
+
+/// Returns the path of frame `n` of a numbered sequence named after `path`,
+/// e.g. `rope-00042.png` for `rope.png`.
+#[must_use]
+pub fn numbered(path: &Path, n: usize) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let mut name = format!("{stem}-{n:05}");
+    if let Some(ext) = path.extension() {
+        name = format!("{name}.{}", ext.to_string_lossy());
+    }
+    path.with_file_name(name)
+}
+
+/// Puts `image` onto a `width` by `height` canvas of `background`, with its
+/// top left cell at `offset`. Parts outside the canvas are cut off.
+#[must_use]
+pub fn place(
+    image: &Grid<Rgb>,
+    offset: Point,
+    width: usize,
+    height: usize,
+    background: Rgb,
+) -> Grid<Rgb> {
+    Grid::from_fn(width, height, |p| {
+        image.get(p - offset).copied().unwrap_or(background)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: Rgb = Rgb::new(255, 0, 0);
+
+    fn image() -> Grid<Rgb> {
+        Grid::new(2, 1, vec![RED, Rgb::WHITE])
+    }
+
+    #[test]
+    fn ppm() {
+        let mut out = vec![];
+        write_ppm(&image(), 2, &mut out).unwrap();
+        let (header, data) = out.split_at(11);
+        assert_eq!(header, b"P6\n4 2\n255\n");
+        assert_eq!(data.len(), 4 * 2 * 3);
+        assert_eq!(
+            data[..12],
+            [255, 0, 0, 255, 0, 0, 255, 255, 255, 255, 255, 255]
+        );
+
+        let err = write_ppm(&image(), usize::MAX, &mut vec![]).unwrap_err();
+        assert!(err.to_string().starts_with("image too large"));
+        assert!(write_ppm(&image(), 1 << 40, &mut vec![]).is_err());
+    }
+
+    #[test]
+    fn png_round_trip() {
+        let mut out = vec![];
+        write_png(&image(), 3, &mut out).unwrap();
+        let decoder = png::Decoder::new(io::Cursor::new(out));
+        let mut reader = decoder.read_info().unwrap();
+        let mut data = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut data).unwrap();
+        assert_eq!((info.width, info.height), (6, 3));
+        assert_eq!(data[..3], [255, 0, 0]);
+        assert_eq!(data[15..18], [255, 255, 255]);
+    }
+
+    #[test]
+    fn gif_frames() {
+        let mut out = vec![];
+        let mut gif = GifWriter::new(&mut out, 2, 1, 1, Duration::from_millis(50)).unwrap();
+        gif.write_frame(&image()).unwrap();
+        gif.write_frame(&image().map(|c| c.mix(Rgb::BLACK, 0.5)))
+            .unwrap();
+        assert!(gif.write_frame(&Grid::filled(1, 1, RED)).is_err());
+        drop(gif);
+
+        let mut decoder = gif::DecodeOptions::new().read_info(&out[..]).unwrap();
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!((frame.width, frame.height, frame.delay), (2, 1, 5));
+            frames += 1;
+        }
+        assert_eq!(frames, 2);
+    }
+
+    #[test]
+    fn formats_and_names() {
+        assert_eq!(Format::from_path(Path::new("a/b.PNG")), Ok(Format::Png));
+        assert!(Format::from_path(Path::new("b.jpg")).is_err());
+        assert_eq!(
+            numbered(Path::new("out/rope.png"), 42),
+            Path::new("out/rope-00042.png")
+        );
+    }
+
+    #[test]
+    fn placed_on_canvas() {
+        let placed = place(&image(), Point::new(1, 1), 3, 2, Rgb::BLACK);
+        assert_eq!(placed.row(0), [Rgb::BLACK; 3]);
+        assert_eq!(placed.row(1), [Rgb::BLACK, RED, Rgb::WHITE]);
+        assert_eq!(Rgb::BLACK.mix(Rgb::WHITE, 0.5), Rgb::new(128, 128, 128));
+    }
+}
@@ -0,0 +1,339 @@
+//! Rectangular grids of cells, addressed by [`Point`]s.
+
+use crate::geom::{Direction, Point};
+use crate::ParseError;
+use std::fmt;
+use std::iter;
+use std::ops;
+
+/// A rectangular grid stored row by row in a single buffer.
+///
+/// Cells are addressed by [`Point`]s with `x` as the column and `y` as the
+/// row, so the first line of a parsed input is row 0.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells, row by row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there aren't exactly `width * height` cells.
+    #[must_use]
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(cells.len(), width * height, "cells don't fill the grid");
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Creates a grid by calling `f` for every point, row by row.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Grid<T> {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Point::new(x.cast_signed(), y.cast_signed())))
+            .map(&mut f)
+            .collect();
+        Grid::new(width, height, cells)
+    }
+
+    /// Parses a grid with one line per row and one character per cell,
+    /// converting the characters with `map`.
+    ///
+    /// # Errors
+    ///
+    /// Fails on empty input, on rows of different lengths and on the first
+    /// character `map` rejects, reporting the message it returned.
+    pub fn parse<E>(
+        input: &str,
+        mut map: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Grid<T>, ParseError>
+    where
+        E: Into<String>,
+    {
+        let mut lines = input.lines().peekable();
+        let width = lines
+            .peek()
+            .ok_or_else(|| ParseError::input("empty grid"))?
+            .chars()
+            .count();
+
+        let mut cells = Vec::with_capacity(input.len());
+        let mut height = 0;
+        for line in lines {
+            let mut row_width = 0;
+            for (pos, c) in line.char_indices() {
+                let cell = map(c).map_err(|e| {
+                    ParseError::new(e, line, &line[pos..pos + c.len_utf8()]).shift_lines(height)
+                })?;
+                cells.push(cell);
+                row_width += 1;
+            }
+            if row_width != width {
+                return Err(ParseError::line(
+                    format!("expected {width} cells like the first row, found {row_width}"),
+                    line,
+                )
+                .shift_lines(height));
+            }
+            height += 1;
+        }
+
+        Ok(Grid::new(width, height, cells))
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the index of `p` in the backing buffer, if it is on the grid.
+    fn index(&self, p: Point) -> Option<usize> {
+        let x = usize::try_from(p.x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(p.y).ok().filter(|&y| y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    /// Returns the point of the cell at `index` in the backing buffer.
+    fn point(&self, index: usize) -> Point {
+        Point::new(
+            (index % self.width).cast_signed(),
+            (index / self.width).cast_signed(),
+        )
+    }
+
+    #[must_use]
+    pub fn contains(&self, p: Point) -> bool {
+        self.index(p).is_some()
+    }
+
+    #[must_use]
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.index(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.index(p).map(|i| &mut self.cells[i])
+    }
+
+    /// Returns all cells together with their points, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.point(i), cell))
+    }
+
+    /// Returns the points of all cells, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.cells.len()).map(|i| self.point(i))
+    }
+
+    /// Returns the cells of row `y`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y` is outside the grid.
+    #[must_use]
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} outside the grid");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Returns the cells of column `x`, top to bottom.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is outside the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} outside the grid");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Returns the orthogonal neighbours of `p` that are on the grid.
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        p.neighbours4()
+            .filter_map(|n| self.get(n).map(|cell| (n, cell)))
+    }
+
+    /// Returns the orthogonal and diagonal neighbours of `p` that are on the
+    /// grid.
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        p.neighbours8()
+            .filter_map(|n| self.get(n).map(|cell| (n, cell)))
+    }
+
+    /// Returns the cells seen when walking from `start` in `direction` up to
+    /// the edge of the grid, not including `start` itself.
+    pub fn ray(&self, start: Point, direction: Direction) -> impl Iterator<Item = (Point, &T)> {
+        iter::successors(Some(start + direction), move |&p| Some(p + direction))
+            .map_while(|p| self.get(p).map(|cell| (p, cell)))
+    }
+
+    /// Converts every cell with `f`.
+    #[must_use]
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Grid<U> {
+        Grid::new(
+            self.width,
+            self.height,
+            self.cells.into_iter().map(f).collect(),
+        )
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    #[must_use]
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /// Mirrors the grid along its main diagonal, turning rows into columns.
+    #[must_use]
+    pub fn transpose(&self) -> Grid<T> {
+        Grid::from_fn(self.height, self.width, |p| {
+            self[Point::new(p.y, p.x)].clone()
+        })
+    }
+
+    /// Rotates the grid a quarter turn clockwise.
+    #[must_use]
+    pub fn rotate_right(&self) -> Grid<T> {
+        let last_row = self.height.cast_signed() - 1;
+        Grid::from_fn(self.height, self.width, |p| {
+            self[Point::new(p.y, last_row - p.x)].clone()
+        })
+    }
+
+    /// Rotates the grid a quarter turn counterclockwise.
+    #[must_use]
+    pub fn rotate_left(&self) -> Grid<T> {
+        let last_col = self.width.cast_signed() - 1;
+        Grid::from_fn(self.height, self.width, |p| {
+            self[Point::new(last_col - p.y, p.x)].clone()
+        })
+    }
+}
+
+impl<T> ops::Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{p} outside of {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> ops::IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{p} outside of {width}x{height} grid"))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    /// Renders the grid like the input it was parsed from, one line per row.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(input: &str) -> Grid<char> {
+        Grid::parse(input, Ok::<_, String>).unwrap()
+    }
+
+    #[test]
+    fn parse_and_render() {
+        let grid = chars("abc\ndef");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], 'f');
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+        assert_eq!(grid.to_string(), "abc\ndef");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let digit = |c: char| c.to_digit(10).ok_or("not a digit");
+        let err = Grid::parse("12\n3x", digit).unwrap_err();
+        assert_eq!(err.position(), Some((2, 2)));
+        let err = Grid::parse("12\n345", digit).unwrap_err();
+        assert_eq!(err.position(), Some((2, 1)));
+        assert!(Grid::parse("", digit).is_err());
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = chars("abc\ndef");
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            [['a', 'b', 'c'], ['d', 'e', 'f']]
+        );
+        let columns = grid
+            .columns()
+            .map(Iterator::collect::<String>)
+            .collect::<Vec<_>>();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+    }
+
+    #[test]
+    fn neighbours_and_rays() {
+        let grid = chars("abc\ndef");
+        let cells =
+            |it: &mut dyn Iterator<Item = (Point, &char)>| it.map(|(_, c)| *c).collect::<String>();
+        assert_eq!(cells(&mut grid.neighbours4(Point::new(0, 0))), "bd");
+        assert_eq!(cells(&mut grid.neighbours8(Point::new(1, 1))), "abcdf");
+        assert_eq!(
+            cells(&mut grid.ray(Point::new(0, 1), Direction::Right)),
+            "ef"
+        );
+        assert_eq!(cells(&mut grid.ray(Point::new(0, 1), Direction::Left)), "");
+    }
+
+    #[test]
+    fn transform() {
+        let grid = chars("abc\ndef");
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+    }
+
+    #[test]
+    fn mutate() {
+        let mut grid = Grid::filled(2, 2, 0);
+        grid[Point::new(1, 0)] = 5;
+        *grid.get_mut(Point::new(0, 1)).unwrap() += 1;
+        assert_eq!(grid.map(|n| n * 2).to_string(), "010\n20");
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{parse, Result};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Command line arguments selecting the puzzle input of a day.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct InputArgs {
+    /// Puzzle input file, or `-` to read from stdin [default: input.txt of the day]
+    pub path: Option<PathBuf>,
+
+    /// Use the bundled example input (sample.txt) instead
+    #[arg(long, conflicts_with = "path")]
+    pub sample: bool,
+}
+
+impl InputArgs {
+    /// Resolves the selected input relative to the day's crate directory.
+    #[must_use]
+    pub fn resolve(&self, day_dir: &Path) -> PathBuf {
+        match &self.path {
+            Some(path) => path.clone(),
+            None if self.sample => day_dir.join("sample.txt"),
+            None => day_dir.join("input.txt"),
+        }
+    }
+
+    /// Reads the selected input.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file or stdin cannot be read.
+    pub fn read(&self, day_dir: &Path) -> Result<String> {
+        read_input(self.resolve(day_dir))
+    }
+
+    /// Opens the selected input for reading line by line.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file cannot be opened.
+    pub fn open(&self, day_dir: &Path) -> Result<Box<dyn BufRead>> {
+        open_input(self.resolve(day_dir))
+    }
+}
+
+/// Opens the puzzle input at `path`, or stdin if `path` is `-`, without
+/// reading it yet.
+///
+/// Unlike [`read_input`] the input is passed on as is; line based parsers
+/// normalize it with [`LineReader`](crate::stream::LineReader).
+///
+/// # Errors
+///
+/// Fails if the file cannot be opened, naming the offending path.
+pub fn open_input(path: impl AsRef<Path>) -> Result<Box<dyn BufRead>> {
+    let path = path.as_ref();
+    if path == Path::new("-") {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {e}", path.display()))?;
+    Ok(Box::new(BufReader::new(file)))
+}
+
+/// Reads the puzzle input at `path` into a string, or stdin if `path` is `-`.
+///
+/// The input is [normalized](parse::normalize), so parsers never see CRLF
+/// line endings, a byte order mark or trailing whitespace.
+///
+/// # Errors
+///
+/// Fails if the input cannot be read, naming the offending path.
+pub fn read_input(path: impl AsRef<Path>) -> Result<String> {
+    let path = path.as_ref();
+    let input = if path == Path::new("-") {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|e| format!("Failed to read stdin: {e}"))?;
+        input
+    } else {
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?
+    };
+    Ok(parse::normalize(&input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_paths() {
+        let dir = Path::new("day05");
+        let mut args = InputArgs::default();
+        assert_eq!(args.resolve(dir), dir.join("input.txt"));
+
+        args.sample = true;
+        assert_eq!(args.resolve(dir), dir.join("sample.txt"));
+
+        args.sample = false;
+        args.path = Some(PathBuf::from("-"));
+        assert_eq!(args.resolve(dir), Path::new("-"));
+    }
+}
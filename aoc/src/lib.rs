@@ -0,0 +1,104 @@
+#![warn(clippy::pedantic)]
+
+//! Helpers shared by all Advent of Code 2022 solutions.
+
+mod answer;
+pub mod answers;
+pub mod export;
+pub mod geom;
+pub mod grid;
+mod input;
+pub mod parse;
+mod solution;
+pub mod stream;
+pub mod topk;
+pub mod visual;
+
+pub use answer::{Answer, Screen};
+pub use input::{open_input, read_input, InputArgs};
+pub use parse::ParseError;
+pub use solution::{Part, Solution};
+
+use clap::Parser;
+use std::error::Error;
+use std::path::Path;
+use std::process::ExitCode;
+
+/// Result type used by the solutions, defaulting to a boxed error.
+pub type Result<T, E = Box<dyn Error>> = std::result::Result<T, E>;
+
+/// Formats a single answer, putting multi-line answers below their label.
+fn format_answer(part: Part, answer: &Answer) -> String {
+    let answer = answer.to_string();
+    if answer.contains('\n') {
+        format!("{part}:\n{answer}")
+    } else {
+        format!("{part}: {answer}")
+    }
+}
+
+/// Prints the answer to one part as `A: ...` or `B: ...`.
+pub fn print_answer(part: Part, answer: &Answer) {
+    println!("{}", format_answer(part, answer));
+}
+
+/// Command line of the individual day binaries.
+#[derive(Parser)]
+#[command(about = "Solves both parts of the day's puzzle")]
+struct DayArgs {
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+/// Solves both parts of `S` for the input at `path` and prints the answers.
+///
+/// # Errors
+///
+/// Fails if the input can't be read, parsed or solved.
+pub fn run<S: Solution>(path: impl AsRef<Path>) -> Result<()> {
+    let input = S::parse_reader(open_input(path)?)?;
+    for part in Part::ALL {
+        print_answer(part, &S::solve(&input, part)?);
+    }
+    Ok(())
+}
+
+/// Turns the outcome of a binary into its exit code, printing errors with
+/// their `Display` impl so parse errors show their snippet.
+#[must_use]
+pub fn exit(result: Result<()>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Entry point of a day binary: picks the input from the command line and
+/// runs both parts of `S`.
+///
+/// Relative default inputs are looked up in `day_dir`, which should be the
+/// day's `CARGO_MANIFEST_DIR`, so the binary works from any directory.
+#[must_use]
+pub fn main<S: Solution>(day_dir: &str) -> ExitCode {
+    let args = DayArgs::parse();
+    exit(run::<S>(args.input.resolve(Path::new(day_dir))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_line_answer() {
+        assert_eq!(format_answer(Part::A, &Answer::Int(42)), "A: 42");
+    }
+
+    #[test]
+    fn multi_line_answer() {
+        let screen = Screen::new(2, vec![true, false, false, true]);
+        assert_eq!(format_answer(Part::B, &screen.into()), "B:\n#.\n.#");
+    }
+}
@@ -0,0 +1,313 @@
+use std::error::Error;
+use std::fmt;
+use std::iter;
+use std::str::FromStr;
+
+/// Position of a [`ParseError`] inside the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Location {
+    /// 1-based line number.
+    line: usize,
+    /// 1-based column, counted in characters.
+    column: usize,
+    /// Number of characters to underline.
+    len: usize,
+    /// The complete offending line.
+    text: String,
+}
+
+/// Error while parsing puzzle input, pointing at the offending text.
+///
+/// Parsers of a single line or block report line numbers relative to their
+/// own input; callers that split a larger input move them into place with
+/// [`ParseError::shift_lines`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+    location: Option<Location>,
+}
+
+/// Returns the byte offset of `span` inside `line` if it is a subslice of it.
+fn offset_in(line: &str, span: &str) -> Option<usize> {
+    let start = (span.as_ptr() as usize).checked_sub(line.as_ptr() as usize)?;
+    (start + span.len() <= line.len()).then_some(start)
+}
+
+impl ParseError {
+    /// Creates an error for `span`, which must be a subslice of `line`.
+    /// Spans from elsewhere underline the whole line instead.
+    pub fn new(message: impl Into<String>, line: &str, span: &str) -> ParseError {
+        let (column, len) = match offset_in(line, span) {
+            Some(start) => (line[..start].chars().count() + 1, span.chars().count()),
+            None => (1, line.chars().count()),
+        };
+        ParseError {
+            message: message.into(),
+            location: Some(Location {
+                line: 1,
+                column,
+                len,
+                text: line.to_string(),
+            }),
+        }
+    }
+
+    /// Creates an error for the whole of `line`.
+    pub fn line(message: impl Into<String>, line: &str) -> ParseError {
+        ParseError::new(message, line, line)
+    }
+
+    /// Creates an error that doesn't refer to a specific line, e.g. for
+    /// missing input.
+    pub fn input(message: impl Into<String>) -> ParseError {
+        ParseError {
+            message: message.into(),
+            location: None,
+        }
+    }
+
+    /// Moves an error reported by a parser of `span` to its place in `line`,
+    /// which contains `span`.
+    #[must_use]
+    pub fn within(mut self, line: &str, span: &str) -> ParseError {
+        if let (Some(location), Some(start)) = (&mut self.location, offset_in(line, span)) {
+            location.column += line[..start].chars().count();
+            location.text = line.to_string();
+        }
+        self
+    }
+
+    /// Moves the error `n` lines down, for parsers of a line or block that
+    /// starts on line `n + 1` of the whole input.
+    #[must_use]
+    pub fn shift_lines(mut self, n: usize) -> ParseError {
+        if let Some(location) = &mut self.location {
+            location.line += n;
+        }
+        self
+    }
+
+    /// Prefixes the message with `context`, e.g. the block of the input the
+    /// error occurred in.
+    #[must_use]
+    pub fn context(mut self, context: &str) -> ParseError {
+        self.message = format!("{context}: {}", self.message);
+        self
+    }
+
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the 1-based line and column of the error, if it has one.
+    #[must_use]
+    pub fn position(&self) -> Option<(usize, usize)> {
+        self.location.as_ref().map(|l| (l.line, l.column))
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(l) = &self.location else {
+            return write!(f, "{}", self.message);
+        };
+
+        let gutter = " ".repeat(l.line.to_string().len());
+        writeln!(f, "line {}, column {}: {}", l.line, l.column, self.message)?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", l.line, l.text)?;
+        write!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(l.column - 1),
+            "^".repeat(l.len.max(1))
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `token`, a subslice of `line`, reporting failures at its position.
+///
+/// # Errors
+///
+/// Fails if `token` can't be parsed as `T`.
+pub fn token<T>(line: &str, token: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    token
+        .parse()
+        .map_err(|e| ParseError::new(format!("cannot parse `{token}`: {e}"), line, token))
+}
+
+/// Parses every line of `input` with `parse`, numbering errors by line.
+///
+/// # Errors
+///
+/// Fails on the first line that can't be parsed.
+pub fn lines<T>(
+    input: &str,
+    parse: impl Fn(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|e| e.shift_lines(i)))
+        .collect()
+}
+
+/// Normalizes puzzle input as saved by different editors and platforms.
+///
+/// Strips a leading byte order mark, turns CRLF line endings into LF, removes
+/// trailing whitespace from every line and drops trailing blank lines
+/// together with the final newline. Line numbers are preserved.
+#[must_use]
+pub fn normalize(input: &str) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let mut normalized = String::with_capacity(input.len());
+    for line in input.lines() {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+    normalized.truncate(normalized.trim_end().len());
+    normalized
+}
+
+/// Splits `s` after its first line, returning the line without its line
+/// ending and the rest of `s`.
+fn split_line(s: &str) -> Option<(&str, &str)> {
+    if s.is_empty() {
+        return None;
+    }
+    Some(match s.split_once('\n') {
+        Some((line, rest)) => (line.strip_suffix('\r').unwrap_or(line), rest),
+        None => (s, ""),
+    })
+}
+
+/// Splits `input` into groups of lines separated by one or more blank lines,
+/// yielding each group together with the number of lines preceding it.
+///
+/// Lines containing only whitespace count as blank, and CRLF line endings
+/// are accepted. The groups are subslices of `input` without their final
+/// line ending.
+pub fn blocks(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    let is_blank = |line: &str| line.trim().is_empty();
+    let mut rest = input;
+    let mut line = 0;
+    iter::from_fn(move || {
+        while let Some((first, tail)) = split_line(rest) {
+            if !is_blank(first) {
+                break;
+            }
+            rest = tail;
+            line += 1;
+        }
+
+        let block = rest;
+        let start = line;
+        let mut len = 0;
+        while let Some((first, tail)) = split_line(rest).filter(|(l, _)| !is_blank(l)) {
+            len = block.len() - rest.len() + first.len();
+            rest = tail;
+            line += 1;
+        }
+        (line > start).then(|| (start, &block[..len]))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caret_snippet() {
+        let line = "move x1 from 2 to 1";
+        let err = ParseError::new("invalid count", line, &line[5..7]).shift_lines(2);
+        assert_eq!(err.position(), Some((3, 6)));
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 6: invalid count\n  |\n3 | move x1 from 2 to 1\n  |      ^^"
+        );
+    }
+
+    #[test]
+    fn foreign_span_underlines_line() {
+        let err = ParseError::new("bad", "abc", "b");
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 1: bad\n  |\n1 | abc\n  | ^^^"
+        );
+    }
+
+    #[test]
+    fn empty_span_at_end() {
+        let line = "addx";
+        let err = ParseError::new("missing value", line, &line[4..]);
+        assert_eq!(err.position(), Some((1, 5)));
+        assert!(err.to_string().ends_with("|     ^"));
+    }
+
+    #[test]
+    fn relocate_into_line() {
+        let line = "addx 1x";
+        let err = ParseError::new("bad digit", &line[5..], &line[6..]).within(line, &line[5..]);
+        assert_eq!(err.position(), Some((1, 7)));
+        assert!(err.to_string().contains("1 | addx 1x\n  |       ^"));
+    }
+
+    #[test]
+    fn message_context() {
+        let err = ParseError::line("bad", "abc")
+            .shift_lines(4)
+            .context("elf 2");
+        assert_eq!(err.message(), "elf 2: bad");
+        assert!(err
+            .to_string()
+            .starts_with("line 5, column 1: elf 2: bad\n"));
+    }
+
+    #[test]
+    fn without_location() {
+        let err = ParseError::input("empty input").shift_lines(3);
+        assert_eq!(err.position(), None);
+        assert_eq!(err.to_string(), "empty input");
+    }
+
+    #[test]
+    fn line_numbers() {
+        let err = lines("1\n2\nx", |l| token::<u32>(l, l)).unwrap_err();
+        assert_eq!(err.position(), Some((3, 1)));
+        assert_eq!(lines("1\n2", |l| token::<u32>(l, l)), Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn block_offsets() {
+        let input = "a\nb\n\nc\n\nd\ne\nf";
+        assert_eq!(
+            blocks(input).collect::<Vec<_>>(),
+            [(0, "a\nb"), (3, "c"), (5, "d\ne\nf")]
+        );
+    }
+
+    #[test]
+    fn blocks_with_blank_runs_and_crlf() {
+        let input = "\r\na\r\nb\r\n \r\n\t\r\nc\n\n\n";
+        assert_eq!(blocks(input).collect::<Vec<_>>(), [(1, "a\r\nb"), (5, "c")]);
+        assert_eq!(blocks("").count(), 0);
+        assert_eq!(blocks("\n \n").count(), 0);
+    }
+
+    #[test]
+    fn normalize_input() {
+        assert_eq!(
+            normalize("\u{feff}1 \r\n2\t\r\n\r\n3\r\n\r\n\n"),
+            "1\n2\n\n3"
+        );
+        assert_eq!(normalize("    [D]    \n[N] [C]    \n"), "    [D]\n[N] [C]");
+        assert_eq!(normalize(" \n\n"), "");
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{parse, Answer, Result};
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// One of the two parts of a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    A,
+    B,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::A, Part::B];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::A => write!(f, "A"),
+            Part::B => write!(f, "B"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Part, String> {
+        match s {
+            "a" | "A" => Ok(Part::A),
+            "b" | "B" => Ok(Part::B),
+            _ => Err(format!("Invalid part `{s}`, expected `a` or `b`")),
+        }
+    }
+}
+
+/// Solution to a single day's puzzle.
+///
+/// The input is parsed once by [`Solution::parse`] or
+/// [`Solution::parse_reader`] and then shared by both parts.
+pub trait Solution {
+    /// Day of the puzzle, starting at 1.
+    const DAY: u8;
+
+    /// Parsed puzzle input.
+    type Input;
+
+    /// Whether [`Solution::parse_reader`] already computes both answers in
+    /// a single pass, leaving the parts only to read them off. Timings and
+    /// allocations of such parts say nothing about the work they do.
+    const SOLVED_WHILE_PARSING: bool = false;
+
+    /// Parses the raw puzzle input.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the input is malformed.
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    /// Parses the puzzle input from `reader`.
+    ///
+    /// By default the whole input is read and [normalized](parse::normalize)
+    /// before calling [`Solution::parse`]. Days whose input is processed line
+    /// by line override this to run in constant memory.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the input can't be read or is malformed.
+    fn parse_reader(mut reader: impl BufRead) -> Result<Self::Input> {
+        let mut input = String::new();
+        reader
+            .read_to_string(&mut input)
+            .map_err(|e| format!("Failed to read input: {e}"))?;
+        Self::parse(&parse::normalize(&input))
+    }
+
+    /// Solves the first part of the puzzle.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the input has no valid answer.
+    fn part_a(input: &Self::Input) -> Result<Answer>;
+
+    /// Solves the second part of the puzzle.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the input has no valid answer.
+    fn part_b(input: &Self::Input) -> Result<Answer>;
+
+    /// Solves `part` of the puzzle.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the input has no valid answer.
+    fn solve(input: &Self::Input, part: Part) -> Result<Answer> {
+        match part {
+            Part::A => Self::part_a(input),
+            Part::B => Self::part_b(input),
+        }
+    }
+}
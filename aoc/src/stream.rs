@@ -0,0 +1,146 @@
+//! Line-by-line reading of puzzle input from any [`BufRead`], for days that
+//! don't need the whole input in memory at once.
+
+use crate::ParseError;
+use std::io::{self, BufRead};
+
+/// Reads numbered lines from a [`BufRead`], normalizing them like
+/// [`normalize`](crate::parse::normalize) does for whole inputs.
+///
+/// Lines are yielded without their line ending or trailing whitespace, a
+/// byte order mark at the start of the input is skipped, and blank lines at
+/// the end are dropped. Only the current line is kept in memory.
+pub struct LineReader<R> {
+    reader: R,
+    line: String,
+    number: usize,
+    /// Blank lines read ahead of `line`, not yet yielded.
+    blanks: usize,
+    /// Whether `line` holds a line that hasn't been yielded yet.
+    buffered: bool,
+}
+
+impl<R: BufRead> LineReader<R> {
+    pub fn new(reader: R) -> LineReader<R> {
+        LineReader {
+            reader,
+            line: String::new(),
+            number: 0,
+            blanks: 0,
+            buffered: false,
+        }
+    }
+
+    /// Reads the next raw line into `line`, returning `false` at the end of
+    /// the input.
+    fn read_line(&mut self) -> io::Result<bool> {
+        let at_start = self.number == 0 && self.blanks == 0;
+        self.line.clear();
+        if self.reader.read_line(&mut self.line)? == 0 {
+            return Ok(false);
+        }
+        if at_start && self.line.starts_with('\u{feff}') {
+            self.line.drain(..'\u{feff}'.len_utf8());
+        }
+        self.line.truncate(self.line.trim_end().len());
+        Ok(true)
+    }
+
+    /// Returns the next line together with its 1-based line number, or
+    /// `None` once only blank lines are left.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the reader fails or the input isn't valid UTF-8.
+    pub fn next_line(&mut self) -> io::Result<Option<(usize, &str)>> {
+        if !self.buffered {
+            loop {
+                if !self.read_line()? {
+                    return Ok(None);
+                }
+                if !self.line.is_empty() {
+                    break;
+                }
+                self.blanks += 1;
+            }
+            self.buffered = true;
+        }
+
+        self.number += 1;
+        if self.blanks > 0 {
+            self.blanks -= 1;
+            Ok(Some((self.number, "")))
+        } else {
+            self.buffered = false;
+            Ok(Some((self.number, &self.line)))
+        }
+    }
+}
+
+/// Calls `f` on every line of `reader`, numbering its errors by line.
+///
+/// # Errors
+///
+/// Fails on the first line `f` rejects. Read errors are reported as a
+/// [`ParseError`] without a position.
+pub fn for_each_line(
+    reader: impl BufRead,
+    mut f: impl FnMut(&str) -> Result<(), ParseError>,
+) -> Result<(), ParseError> {
+    let mut lines = LineReader::new(reader);
+    while let Some((number, line)) = lines
+        .next_line()
+        .map_err(|e| ParseError::input(format!("cannot read input: {e}")))?
+    {
+        f(line).map_err(|e| e.shift_lines(number - 1))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(input: &str) -> Vec<(usize, String)> {
+        let mut reader = LineReader::new(input.as_bytes());
+        let mut lines = vec![];
+        while let Some((n, line)) = reader.next_line().unwrap() {
+            lines.push((n, line.to_string()));
+        }
+        lines
+    }
+
+    #[test]
+    fn normalized_lines() {
+        assert_eq!(
+            lines("\u{feff}a \r\n\r\n  \nb\t\r\n\n \n"),
+            [(1, "a"), (2, ""), (3, ""), (4, "b")].map(|(n, l)| (n, l.to_string()))
+        );
+        assert_eq!(
+            lines("\n\na"),
+            [(1, ""), (2, ""), (3, "a")].map(|(n, l)| (n, l.to_string()))
+        );
+        assert!(lines("").is_empty());
+        assert!(lines("\n \r\n").is_empty());
+    }
+
+    #[test]
+    fn same_as_normalize() {
+        let input = "\u{feff}1 2\r\n\r\n3\n \n4  \n\n";
+        let streamed = lines(input).into_iter().map(|(_, l)| l).collect::<Vec<_>>();
+        assert_eq!(streamed.join("\n"), crate::parse::normalize(input));
+    }
+
+    #[test]
+    fn errors_numbered_by_line() {
+        let err = for_each_line("ok\n\nok\nbad".as_bytes(), |line| match line {
+            "bad" => Err(ParseError::new("bad line", line, &line[1..])),
+            _ => Ok(()),
+        })
+        .unwrap_err();
+        assert_eq!(err.position(), Some((4, 2)));
+
+        let err = for_each_line(&[b'a', 0xff][..], |_| Ok(())).unwrap_err();
+        assert_eq!(err.position(), None);
+    }
+}
@@ -0,0 +1,117 @@
+//! The largest items of a stream, kept in bounded memory.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// The `k` largest items pushed so far.
+///
+/// Items are kept in a min-heap of at most `k` entries, so memory stays
+/// O(k) however many items are pushed, and each push takes O(log k).
+#[derive(Debug, Clone)]
+pub struct TopK<T> {
+    k: usize,
+    heap: BinaryHeap<Reverse<T>>,
+}
+
+impl<T: Ord> TopK<T> {
+    #[must_use]
+    pub fn new(k: usize) -> TopK<T> {
+        TopK {
+            k,
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    /// Returns the number of items kept at most.
+    #[must_use]
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Returns the number of items kept, less than `k` until `k` items were
+    /// pushed.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Adds `item`, dropping the smallest item kept if there are more than
+    /// `k`.
+    pub fn push(&mut self, item: T) {
+        if self.heap.len() < self.k {
+            self.heap.push(Reverse(item));
+        } else if let Some(mut min) = self.heap.peek_mut() {
+            if item > min.0 {
+                *min = Reverse(item);
+            }
+        }
+    }
+
+    /// Returns the smallest of the items kept.
+    #[must_use]
+    pub fn min(&self) -> Option<&T> {
+        self.heap.peek().map(|Reverse(item)| item)
+    }
+
+    /// Returns the items kept, largest first.
+    #[must_use]
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        // The heap sorts by `Reverse`, so ascending order is largest first.
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(item)| item)
+            .collect()
+    }
+}
+
+impl<T: Ord> Extend<T> for TopK<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        for item in items {
+            self.push(item);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_largest() {
+        let mut top = TopK::new(3);
+        assert_eq!(top.min(), None);
+        top.extend([5, 1, 9, 3, 7, 9, 2]);
+        assert_eq!((top.k(), top.len()), (3, 3));
+        assert_eq!(top.min(), Some(&7));
+        assert_eq!(top.into_sorted_vec(), [9, 9, 7]);
+    }
+
+    #[test]
+    fn fewer_items_than_k() {
+        let mut top = TopK::new(5);
+        top.extend([2, 4]);
+        assert_eq!(top.into_sorted_vec(), [4, 2]);
+
+        let mut none = TopK::new(0);
+        none.push(1);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn same_as_sorting() {
+        let items = (0..1000u64).map(|i| i * 7919 % 1009).collect::<Vec<_>>();
+        let mut sorted = items.clone();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+        for k in [1, 2, 10, 999, 1000, 2000] {
+            let mut top = TopK::new(k);
+            top.extend(items.iter().copied());
+            assert_eq!(top.into_sorted_vec(), sorted[..k.min(1000)], "k = {k}");
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Step-by-step visualization of simulations.
+//!
+//! A simulation implements [`Visualize`] to draw its current state as a
+//! [`Frame`] of characters and to advance one step at a time. Renderers, like
+//! the terminal player of the runner, only ever deal with frames.
+
+use crate::geom::Point;
+use crate::grid::Grid;
+
+/// A picture of a simulation's state: a grid of characters and a caption
+/// describing the current step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub caption: String,
+    pub cells: Grid<char>,
+    /// Cell to keep in view when the frame doesn't fit the display.
+    pub focus: Option<Point>,
+    /// Position of the top left cell in the simulation's own coordinates,
+    /// which lines up frames that cover different areas.
+    pub origin: Point,
+}
+
+impl Frame {
+    #[must_use]
+    pub fn new(caption: impl Into<String>, cells: Grid<char>) -> Frame {
+        Frame {
+            caption: caption.into(),
+            cells,
+            focus: None,
+            origin: Point::ORIGIN,
+        }
+    }
+
+    /// Creates a frame from lines of text, padding short lines with spaces.
+    #[must_use]
+    pub fn from_text(caption: impl Into<String>, text: &str) -> Frame {
+        let lines = text
+            .lines()
+            .map(|l| l.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let width = lines.iter().map(Vec::len).max().unwrap_or(0);
+        let cells = Grid::from_fn(width, lines.len(), |p| {
+            lines[p.y.unsigned_abs()]
+                .get(p.x.unsigned_abs())
+                .copied()
+                .unwrap_or(' ')
+        });
+        Frame::new(caption, cells)
+    }
+
+    #[must_use]
+    pub fn with_focus(mut self, focus: Point) -> Frame {
+        self.focus = Some(focus);
+        self
+    }
+
+    #[must_use]
+    pub fn with_origin(mut self, origin: Point) -> Frame {
+        self.origin = origin;
+        self
+    }
+
+    /// Returns the lines of the part of the frame that fits into `width`
+    /// columns and `height` rows, keeping the focus, or else the centre, in
+    /// view.
+    #[must_use]
+    pub fn viewport(&self, width: usize, height: usize) -> Vec<String> {
+        let focus = self.focus.unwrap_or_else(|| {
+            Point::new(
+                (self.cells.width() / 2).cast_signed(),
+                (self.cells.height() / 2).cast_signed(),
+            )
+        });
+        let start = |size: usize, fit: usize, focus: isize| {
+            let max = size.saturating_sub(fit);
+            focus
+                .saturating_sub((fit / 2).cast_signed())
+                .clamp(0, max.cast_signed())
+                .unsigned_abs()
+        };
+        let x0 = start(self.cells.width(), width, focus.x);
+        let y0 = start(self.cells.height(), height, focus.y);
+
+        self.cells
+            .rows()
+            .skip(y0)
+            .take(height)
+            .map(|row| row.iter().skip(x0).take(width).collect())
+            .collect()
+    }
+}
+
+/// A simulation that can be watched one step at a time.
+pub trait Visualize {
+    /// Draws the current state.
+    fn frame(&self) -> Frame;
+
+    /// Advances the simulation by one step, returning `false` once there is
+    /// nothing left to do.
+    fn step(&mut self) -> bool;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_frames_are_padded() {
+        let frame = Frame::from_text("test", "ab\n\nc");
+        assert_eq!(frame.cells.to_string(), "ab\n  \nc ");
+    }
+
+    #[test]
+    fn viewport_follows_focus() {
+        let frame = Frame::from_text("", "abcde\nfghij\nklmno");
+        assert_eq!(frame.viewport(10, 10), ["abcde", "fghij", "klmno"]);
+        assert_eq!(frame.viewport(3, 1), ["ghi"]);
+
+        let frame = frame.with_focus(Point::new(4, 0));
+        assert_eq!(frame.viewport(2, 2), ["de", "ij"]);
+        let frame = frame.with_focus(Point::new(0, 2));
+        assert_eq!(frame.viewport(2, 2), ["fg", "kl"]);
+    }
+}
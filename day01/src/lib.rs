@@ -0,0 +1,177 @@
+#![warn(missing_docs)]
+
+//! Day 1: Calorie Counting, the calories carried by each elf.
+
+use aoc::stream::for_each_line;
+use aoc::topk::TopK;
+use aoc::{parse, Answer, ParseError, Solution};
+use std::io::BufRead;
+
+pub mod parallel;
+pub mod report;
+
+/// The food items carried by one elf.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elf {
+    /// Position of the elf in the input, starting at 1.
+    pub index: usize,
+    /// Number of food items.
+    pub items: usize,
+    /// Total calories of the items.
+    pub calories: u64,
+}
+
+/// Calls `f` with each elf, in the order of the input.
+///
+/// Elves are separated by one or more blank lines. Only the running total of
+/// the current elf is kept while reading.
+///
+/// # Errors
+///
+/// Fails on the first line that isn't a number or makes an elf's total overflow,
+/// naming the elf.
+pub fn for_each_elf(input: impl BufRead, mut f: impl FnMut(Elf)) -> Result<(), ParseError> {
+    let mut index = 0;
+    let mut elf = None::<Elf>;
+    for_each_line(input, |line| {
+        if line.is_empty() {
+            if let Some(elf) = elf.take() {
+                f(elf);
+            }
+            return Ok(());
+        }
+        let current = elf.get_or_insert_with(|| {
+            index += 1;
+            Elf {
+                index,
+                items: 0,
+                calories: 0,
+            }
+        });
+        let index = current.index;
+        let context = |e: ParseError| e.context(&format!("elf {index}"));
+        let cals = parse::token::<u64>(line, line).map_err(context)?;
+        let overflow = || context(ParseError::line("total calories overflow", line));
+        current.calories = current.calories.checked_add(cals).ok_or_else(overflow)?;
+        current.items += 1;
+        Ok(())
+    })?;
+    if let Some(elf) = elf {
+        f(elf);
+    }
+    Ok(())
+}
+
+/// Returns the `k` largest totals of the calories carried by one elf,
+/// largest first, keeping no more than `k` totals in memory. With fewer than
+/// `k` elves, the totals of all of them are returned.
+///
+/// # Errors
+///
+/// Fails like [`for_each_elf`].
+pub fn top_elf_calories(input: impl BufRead, k: usize) -> Result<Vec<u64>, ParseError> {
+    let mut top = TopK::new(k);
+    for_each_elf(input, |elf| top.push(elf.calories))?;
+    Ok(top.into_sorted_vec())
+}
+
+/// The solution: the most calories carried by one elf, then by the top three.
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const SOLVED_WHILE_PARSING: bool = true;
+    /// The three largest totals, largest first, fewer if there are fewer
+    /// elves.
+    type Input = Vec<u64>;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Self::parse_reader(input.as_bytes())
+    }
+
+    fn parse_reader(input: impl BufRead) -> aoc::Result<Self::Input> {
+        Ok(top_elf_calories(input, 3)?)
+    }
+
+    fn part_a(data: &Self::Input) -> aoc::Result<Answer> {
+        Ok((*data.first().ok_or("No elves in input")?).into())
+    }
+
+    fn part_b(data: &Self::Input) -> aoc::Result<Answer> {
+        let top3 = data.get(..3).ok_or("Fewer than three elves in input")?;
+        let sum = top3.iter().try_fold(0u64, |sum, &c| sum.checked_add(c));
+        Ok(sum.ok_or("Sum of the calories overflows")?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_calories() {
+        let err = top_elf_calories("1\n2\n\n3\nx".as_bytes(), 3).unwrap_err();
+        assert_eq!(err.position(), Some((5, 1)));
+        assert_eq!(
+            err.message(),
+            "elf 2: cannot parse `x`: invalid digit found in string"
+        );
+
+        let err = top_elf_calories("1\n\n\n-4".as_bytes(), 3).unwrap_err();
+        assert_eq!(err.position(), Some((4, 1)));
+        assert!(err.message().starts_with("elf 2: "));
+    }
+
+    #[test]
+    fn large_totals() {
+        let top = top_elf_calories("4000000000\n4000000000\n\n1".as_bytes(), 3).unwrap();
+        assert_eq!(top, [8_000_000_000, 1]);
+
+        let input = format!("1\n\n{}\n1", u64::MAX);
+        let err = top_elf_calories(input.as_bytes(), 3).unwrap_err();
+        assert_eq!(err.position(), Some((4, 1)));
+        assert_eq!(err.message(), "elf 2: total calories overflow");
+
+        let top = vec![u64::MAX, 1, 1];
+        assert!(Day01::part_a(&top).is_ok());
+        assert!(Day01::part_b(&top).is_err());
+    }
+
+    #[test]
+    fn fewer_than_three_elves() {
+        let top = Day01::parse("1\n2\n\n3").unwrap();
+        assert_eq!(top, [3, 3]);
+        assert_eq!(Day01::part_a(&top).unwrap().to_string(), "3");
+        assert!(Day01::part_b(&top).is_err());
+        assert!(Day01::part_a(&Day01::parse("").unwrap()).is_err());
+    }
+
+    #[test]
+    fn keeps_three_totals() {
+        let input = (1..=1000).map(|c| c.to_string()).collect::<Vec<_>>();
+        let top = Day01::parse(&input.join("\n\n")).unwrap();
+        assert_eq!(top, [1000, 999, 998]);
+        assert_eq!(Day01::part_b(&top).unwrap().to_string(), "2997");
+    }
+
+    #[test]
+    fn blank_line_runs() {
+        let mut elves = vec![];
+        for_each_elf("\n1\n2\n\n \n\n4\n\n".as_bytes(), |e| elves.push(e)).unwrap();
+        let elf = |index, items, calories| Elf {
+            index,
+            items,
+            calories,
+        };
+        assert_eq!(elves, [elf(1, 2, 3), elf(2, 1, 4)]);
+    }
+
+    #[test]
+    fn top_k() {
+        let input = include_str!("../sample.txt");
+        let top = |k| top_elf_calories(input.as_bytes(), k).unwrap();
+        assert_eq!(top(1), [24000]);
+        assert_eq!(top(3), [24000, 11000, 10000]);
+        assert_eq!(top(10), [24000, 11000, 10000, 6000, 4000]);
+    }
+}
@@ -1,18 +1,90 @@
-use std::error::Error;
-use std::fs;
+use aoc::{InputArgs, Part, Solution};
+use clap::Parser;
+use day01::parallel::par_top_elf_calories;
+use day01::report::{format_csv, format_report};
+use day01::{for_each_elf, top_elf_calories, Day01, Elf};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let mut data: Vec<u32> = fs::read_to_string("input.txt")?
-        .split("\n\n")
-        .map(|elf| elf.lines().map(|cals| cals.parse::<u32>().unwrap()).sum())
-        .collect();
+#[derive(Parser)]
+#[command(about = "Solves both parts of the day's puzzle")]
+struct Args {
+    #[command(flatten)]
+    input: InputArgs,
+    /// List the K largest totals of calories and their sum instead
+    #[arg(long, value_name = "K")]
+    top: Option<NonZeroUsize>,
+    /// List every elf with statistics and a histogram of the totals instead
+    #[arg(long, conflicts_with_all = ["top", "csv"])]
+    report: bool,
+    /// List every elf as CSV instead
+    #[arg(long, conflicts_with = "top")]
+    csv: bool,
+    /// Read the input file with N threads, 0 for one per CPU (stdin is read
+    /// by one thread)
+    #[arg(long, value_name = "N", conflicts_with_all = ["report", "csv"])]
+    threads: Option<usize>,
+}
+
+/// Returns the `k` largest totals, read with `threads` threads if given.
+fn top_totals(path: &Path, k: usize, threads: Option<usize>) -> aoc::Result<Vec<u64>> {
+    match threads {
+        Some(threads) if path != Path::new("-") => {
+            let threads = match threads {
+                0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+                n => n,
+            };
+            par_top_elf_calories(path, k, threads)
+        }
+        _ => Ok(top_elf_calories(aoc::open_input(path)?, k)?),
+    }
+}
+
+/// Solves both parts like [`aoc::run`], reading the input with `threads`
+/// threads.
+fn run_parallel(path: &Path, threads: usize) -> aoc::Result<()> {
+    let top = top_totals(path, 3, Some(threads))?;
+    for part in Part::ALL {
+        aoc::print_answer(part, &Day01::solve(&top, part)?);
+    }
+    Ok(())
+}
 
-    data.sort();
-    data.reverse();
-    let max = data[0];
-    let top3 = &data[..3].iter().sum::<u32>();
+/// Prints the `k` largest totals, largest first, and their sum.
+fn print_top(path: &Path, k: usize, threads: Option<usize>) -> aoc::Result<()> {
+    let top = top_totals(path, k, threads)?;
+    for (rank, total) in (1..).zip(&top) {
+        println!("{rank}: {total}");
+    }
+    if top.len() < k {
+        println!("only {} elves in input", top.len());
+    }
+    println!("sum: {}", top.iter().map(|&c| u128::from(c)).sum::<u128>());
+    Ok(())
+}
 
-    println!("A: {max}");
-    println!("B: {top3}");
+/// Prints every elf with `format`.
+fn print_elves(path: PathBuf, format: fn(&[Elf]) -> String) -> aoc::Result<()> {
+    let mut elves = vec![];
+    for_each_elf(aoc::open_input(path)?, |elf| elves.push(elf))?;
+    print!("{}", format(&elves));
     Ok(())
 }
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let path = args.input.resolve(Path::new(env!("CARGO_MANIFEST_DIR")));
+    aoc::exit(if let Some(k) = args.top {
+        print_top(&path, k.get(), args.threads)
+    } else if args.report {
+        print_elves(path, format_report)
+    } else if args.csv {
+        print_elves(path, format_csv)
+    } else if let Some(threads) = args.threads {
+        run_parallel(&path, threads)
+    } else {
+        aoc::run::<Day01>(path)
+    })
+}
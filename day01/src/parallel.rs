@@ -0,0 +1,185 @@
+//! Reading huge inputs with several threads, each summing the elves of its
+//! own part of the file.
+
+use crate::{for_each_elf, top_elf_calories};
+use aoc::topk::TopK;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::thread;
+
+/// Returns the offset of the first blank line starting at or after `offset`
+/// that isn't the line containing `offset`, or `len` if there is none.
+fn next_blank_line(file: &File, offset: u64, len: u64) -> io::Result<u64> {
+    let mut reader = BufReader::new(file);
+    reader.seek(SeekFrom::Start(offset))?;
+    let mut line = vec![];
+    // The line containing `offset` may be cut anywhere, skip it.
+    let mut pos = offset + reader.read_until(b'\n', &mut line)? as u64;
+    loop {
+        line.clear();
+        let read = reader.read_until(b'\n', &mut line)?;
+        if read == 0 {
+            return Ok(len);
+        }
+        // Blank the way `aoc::stream::LineReader` sees it.
+        if std::str::from_utf8(&line).is_ok_and(|l| l.trim_end().is_empty()) {
+            return Ok(pos);
+        }
+        pos += read as u64;
+    }
+}
+
+/// Splits the file of `len` bytes into at most `chunks` ranges of about
+/// equal size, each but the first starting at a blank line so that no elf
+/// is cut in two.
+///
+/// # Errors
+///
+/// Fails if the file can't be read.
+pub fn split_on_blank_lines(file: &File, len: u64, chunks: usize) -> io::Result<Vec<(u64, u64)>> {
+    let chunks = u64::try_from(chunks.max(1)).unwrap_or(u64::MAX);
+    let mut starts = vec![0];
+    for i in 1..chunks {
+        let nominal =
+            u64::try_from(u128::from(len) * u128::from(i) / u128::from(chunks)).unwrap_or(len);
+        let last = starts[starts.len() - 1];
+        if nominal <= last {
+            continue;
+        }
+        let start = next_blank_line(file, nominal, len)?;
+        if start >= len {
+            break;
+        }
+        starts.push(start);
+    }
+    let ends = starts.iter().skip(1).copied().chain([len]);
+    Ok(starts.iter().copied().zip(ends).collect())
+}
+
+/// Returns the `k` largest totals of the elves in `range` of the file, none
+/// if it can't be read or parsed.
+fn chunk_top(path: &Path, (start, end): (u64, u64), k: usize) -> Option<TopK<u64>> {
+    let mut file = File::open(path).ok()?;
+    file.seek(SeekFrom::Start(start)).ok()?;
+    let mut top = TopK::new(k);
+    for_each_elf(BufReader::new(file.take(end - start)), |elf| {
+        top.push(elf.calories);
+    })
+    .ok()?;
+    Some(top)
+}
+
+/// Like [`top_elf_calories`], but reads the file at `path` with up to
+/// `threads` threads, each summing the elves of one part of the file and
+/// keeping its own `k` largest totals, which are merged at the end.
+///
+/// The result is the same as that of [`top_elf_calories`]. Errors are
+/// reported by reading the file again sequentially, so their line numbers
+/// and elves are those of the whole file.
+///
+/// # Errors
+///
+/// Fails if the file can't be read, or like [`top_elf_calories`].
+pub fn par_top_elf_calories(path: &Path, k: usize, threads: usize) -> aoc::Result<Vec<u64>> {
+    let open = || File::open(path).map_err(|e| format!("Failed to open {}: {e}", path.display()));
+    let file = open()?;
+    let len = file.metadata()?.len();
+    let ranges = split_on_blank_lines(&file, len, threads)?;
+
+    let tops = thread::scope(|s| {
+        let handles = ranges
+            .iter()
+            .map(|&range| s.spawn(move || chunk_top(path, range, k)))
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|h| h.join().ok().flatten())
+            .collect::<Option<Vec<_>>>()
+    });
+
+    match tops {
+        Some(tops) => {
+            let mut merged = TopK::new(k);
+            for top in tops {
+                merged.extend(top.into_sorted_vec());
+            }
+            Ok(merged.into_sorted_vec())
+        }
+        None => Ok(top_elf_calories(BufReader::new(open()?), k)?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Write;
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// Writes `input` to a file removed when the returned guard is dropped.
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str, input: &str) -> TempFile {
+            let path = std::env::temp_dir().join(format!("day01-{name}-{}", std::process::id()));
+            fs::write(&path, input).unwrap();
+            TempFile(path)
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    /// An input with runs of blank lines, whitespace-only lines and CRLF
+    /// line endings.
+    fn messy_input(elves: u64) -> String {
+        let mut input = "\u{feff}".to_string();
+        let mut seed = 12345u64;
+        for elf in 0..elves {
+            for _ in 0..=elf % 5 {
+                seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+                let ending = if seed.is_multiple_of(7) { "\r\n" } else { "\n" };
+                write!(input, "{}{ending}", seed >> 48).unwrap();
+            }
+            input.push_str(["\n", "\n\n", " \n", "\r\n\t\n"][usize::try_from(elf % 4).unwrap()]);
+        }
+        input
+    }
+
+    #[test]
+    fn same_as_sequential() {
+        let input = messy_input(500);
+        let file = TempFile::new("same", &input);
+        for k in [1, 3, 50, 1000] {
+            let expected = top_elf_calories(input.as_bytes(), k).unwrap();
+            for threads in [1, 2, 3, 8, 100] {
+                let top = par_top_elf_calories(&file.0, k, threads).unwrap();
+                assert_eq!(top, expected, "k = {k}, threads = {threads}");
+            }
+        }
+    }
+
+    #[test]
+    fn chunks_start_at_blank_lines() {
+        let input = "1\n2\n\n3\n4\n\n\n5\n";
+        let file = TempFile::new("chunks", input);
+        let split = |len, chunks| split_on_blank_lines(&File::open(&file.0).unwrap(), len, chunks);
+        assert_eq!(split(13, 4).unwrap(), [(0, 4), (4, 9), (9, 13)]);
+        assert_eq!(split(13, 1).unwrap(), [(0, 13)]);
+        assert_eq!(split(0, 4).unwrap(), [(0, 0)]);
+    }
+
+    #[test]
+    fn same_errors_as_sequential() {
+        let mut input = messy_input(200);
+        input.insert_str(input.len() / 2, "\nx\n");
+        let file = TempFile::new("errors", &input);
+        let expected = top_elf_calories(input.as_bytes(), 3).unwrap_err();
+        let err = par_top_elf_calories(&file.0, 3, 4).unwrap_err();
+        assert_eq!(err.to_string(), expected.to_string());
+    }
+}
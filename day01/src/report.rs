@@ -0,0 +1,239 @@
+//! A report of the calories carried by every elf: one row per elf, summary
+//! statistics of the totals and a histogram, or the rows as CSV.
+
+use crate::Elf;
+use std::fmt::Write;
+
+/// Summary statistics of the calories carried by a non-empty list of elves.
+#[derive(Debug, Clone)]
+pub struct Stats {
+    /// Totals of the elves, smallest first.
+    sorted: Vec<u64>,
+    items: usize,
+}
+
+// Statistics are computed in `f64`, rounding totals beyond 2^53.
+#[allow(clippy::cast_precision_loss)]
+impl Stats {
+    /// Returns the statistics of `elves`, none if there are no elves.
+    #[must_use]
+    pub fn new(elves: &[Elf]) -> Option<Stats> {
+        if elves.is_empty() {
+            return None;
+        }
+        let mut sorted = elves.iter().map(|e| e.calories).collect::<Vec<_>>();
+        sorted.sort_unstable();
+        let items = elves.iter().map(|e| e.items).sum();
+        Some(Stats { sorted, items })
+    }
+
+    /// Returns the number of elves.
+    #[must_use]
+    pub fn elves(&self) -> usize {
+        self.sorted.len()
+    }
+
+    /// Returns the number of food items of all elves.
+    #[must_use]
+    pub fn items(&self) -> usize {
+        self.items
+    }
+
+    /// Returns the calories of all elves.
+    #[must_use]
+    pub fn total(&self) -> u128 {
+        self.sorted.iter().map(|&c| u128::from(c)).sum()
+    }
+
+    /// Returns the smallest total.
+    #[must_use]
+    pub fn min(&self) -> u64 {
+        self.sorted[0]
+    }
+
+    /// Returns the largest total.
+    #[must_use]
+    pub fn max(&self) -> u64 {
+        self.sorted[self.sorted.len() - 1]
+    }
+
+    /// Returns the mean of the totals.
+    #[must_use]
+    pub fn mean(&self) -> f64 {
+        self.total() as f64 / self.elves() as f64
+    }
+
+    /// Returns the median of the totals, the mean of the two middle ones for
+    /// an even number of elves.
+    #[must_use]
+    pub fn median(&self) -> f64 {
+        self.percentile(50.0)
+    }
+
+    /// Returns the `p`th percentile of the totals, interpolating linearly
+    /// between the two closest ranks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` isn't between 0 and 100.
+    #[must_use]
+    pub fn percentile(&self, p: f64) -> f64 {
+        assert!((0.0..=100.0).contains(&p), "percentile {p} out of range");
+        let rank = p / 100.0 * (self.elves() - 1) as f64;
+        // `rank` is between 0 and the last index.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+        let (a, b) = (self.sorted[lo] as f64, self.sorted[hi] as f64);
+        a + (b - a) * (rank - rank.floor())
+    }
+
+    /// Returns the population standard deviation of the totals.
+    #[must_use]
+    pub fn std_dev(&self) -> f64 {
+        let mean = self.mean();
+        let squares = self
+            .sorted
+            .iter()
+            .map(|&c| (c as f64 - mean).powi(2))
+            .sum::<f64>();
+        (squares / self.elves() as f64).sqrt()
+    }
+}
+
+/// Draws a histogram of the totals of `elves` with `bins` bars of equal
+/// range between the smallest and the largest total, the longest bar being
+/// `width` characters.
+///
+/// # Panics
+///
+/// Panics if `bins` is 0.
+#[must_use]
+pub fn histogram(elves: &[Elf], bins: usize, width: usize) -> String {
+    assert!(bins > 0, "a histogram needs at least one bin");
+    let Some(stats) = Stats::new(elves) else {
+        return String::new();
+    };
+    let (min, max) = (stats.min(), stats.max());
+    let bins = u64::try_from(bins).unwrap_or(u64::MAX);
+    // The range of `max - min + 1` totals divided by `bins`, rounded up.
+    let step = (max - min) / bins + 1;
+    let bins = (max - min) / step + 1;
+
+    let mut counts = vec![0; usize::try_from(bins).unwrap_or(usize::MAX)];
+    for elf in elves {
+        let bin = (elf.calories - min) / step;
+        counts[usize::try_from(bin).unwrap_or(usize::MAX)] += 1;
+    }
+    let most = counts.iter().copied().max().unwrap_or(1);
+    let digits = max.to_string().len();
+
+    let mut text = String::new();
+    for (lo, count) in (0..).map(|i| min + i * step).zip(counts) {
+        let hi = lo.saturating_add(step - 1).min(max);
+        // Any elf at all shows as at least one character.
+        let bar = "#".repeat((count * width).div_ceil(most));
+        let line = format!("{lo:>digits$} - {hi:>digits$} | {bar:<width$} {count}");
+        writeln!(text, "{}", line.trim_end()).unwrap();
+    }
+    text
+}
+
+/// Formats one row per elf, followed by the summary statistics and a
+/// histogram of the totals.
+#[must_use]
+pub fn format_report(elves: &[Elf]) -> String {
+    let Some(stats) = Stats::new(elves) else {
+        return "No elves in input\n".to_string();
+    };
+    let mut text = String::new();
+    writeln!(text, "{:>6} {:>6} {:>10}", "elf", "items", "calories").unwrap();
+    for elf in elves {
+        writeln!(
+            text,
+            "{:>6} {:>6} {:>10}",
+            elf.index, elf.items, elf.calories
+        )
+        .unwrap();
+    }
+
+    writeln!(text).unwrap();
+    let rows = [
+        ("elves", stats.elves().to_string()),
+        ("items", stats.items().to_string()),
+        ("total", stats.total().to_string()),
+        ("min", stats.min().to_string()),
+        ("max", stats.max().to_string()),
+        ("mean", format!("{:.1}", stats.mean())),
+        ("median", format!("{:.1}", stats.median())),
+        ("p10", format!("{:.1}", stats.percentile(10.0))),
+        ("p25", format!("{:.1}", stats.percentile(25.0))),
+        ("p75", format!("{:.1}", stats.percentile(75.0))),
+        ("p90", format!("{:.1}", stats.percentile(90.0))),
+        ("std dev", format!("{:.1}", stats.std_dev())),
+    ];
+    for (name, value) in rows {
+        writeln!(text, "{:<8} {value}", format!("{name}:")).unwrap();
+    }
+
+    writeln!(text).unwrap();
+    text.push_str(&histogram(elves, 10, 40));
+    text
+}
+
+/// Formats one row per elf as CSV, with a header row.
+#[must_use]
+pub fn format_csv(elves: &[Elf]) -> String {
+    let mut text = "elf,items,calories\n".to_string();
+    for elf in elves {
+        writeln!(text, "{},{},{}", elf.index, elf.items, elf.calories).unwrap();
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::for_each_elf;
+
+    fn sample() -> Vec<Elf> {
+        let mut elves = vec![];
+        for_each_elf(include_str!("../sample.txt").as_bytes(), |e| elves.push(e)).unwrap();
+        elves
+    }
+
+    #[test]
+    fn sample_stats() {
+        let stats = Stats::new(&sample()).unwrap();
+        assert_eq!(
+            (stats.elves(), stats.items(), stats.total()),
+            (5, 10, 55000)
+        );
+        assert_eq!((stats.min(), stats.max()), (4000, 24000));
+        assert!((stats.mean() - 11000.0).abs() < 1e-9);
+        assert!((stats.median() - 10000.0).abs() < 1e-9);
+        assert!((stats.percentile(25.0) - 6000.0).abs() < 1e-9);
+        assert!((stats.percentile(90.0) - 18800.0).abs() < 1e-9);
+        assert!((stats.std_dev() - 6_985.699_678_629_2).abs() < 1e-6);
+        assert!(Stats::new(&[]).is_none());
+    }
+
+    #[test]
+    fn sample_histogram() {
+        assert_eq!(
+            histogram(&sample(), 4, 6),
+            " 4000 -  9000 | ###### 2
+ 9001 - 14001 | ###### 2
+14002 - 19002 |        0
+19003 - 24000 | ###    1
+"
+        );
+    }
+
+    #[test]
+    fn sample_csv() {
+        assert_eq!(
+            format_csv(&sample()),
+            "elf,items,calories\n1,3,6000\n2,1,4000\n3,2,11000\n4,3,24000\n5,1,10000\n"
+        );
+    }
+}
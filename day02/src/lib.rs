@@ -0,0 +1,216 @@
+#![warn(clippy::pedantic)]
+#![warn(missing_docs)]
+
+//! Day 2: Rock Paper Scissors, scoring a strategy guide.
+
+use aoc::stream::for_each_line;
+use aoc::{Answer, ParseError, Solution};
+use std::io::BufRead;
+
+pub mod rules;
+
+pub use rules::{Goal, Move, Rules};
+
+/// A single line of the strategy guide, with its second column read both as
+/// a move and as a goal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
+    /// The move the opponent plays, from the first column.
+    pub opponent: Move,
+    /// The second column read as the move to play.
+    pub response: Move,
+    /// The second column read as how the round should end, none if the rules
+    /// have more moves than goals and it isn't one.
+    pub goal: Option<Goal>,
+}
+
+impl Round {
+    /// Parses a line of the strategy guide with the symbols of `rules`.
+    ///
+    /// # Errors
+    ///
+    /// Fails unless the line is two symbols separated by a space, the first
+    /// an opponent's move and the second a move of the player.
+    pub fn parse(rules: &Rules, input: &str) -> Result<Round, ParseError> {
+        let (x, y) = input
+            .split_once(' ')
+            .ok_or_else(|| ParseError::line("expected two symbols separated by a space", input))?;
+        let invalid = |message: &str, symbol: &str| {
+            ParseError::new(format!("invalid {message} `{symbol}`"), input, symbol)
+        };
+        Ok(Round {
+            opponent: rules.opponent_move(x).ok_or_else(|| invalid("move", x))?,
+            response: rules.response_move(y).ok_or_else(|| invalid("move", y))?,
+            goal: rules.goal(y),
+        })
+    }
+
+    /// Returns the score of the round when the second column is the move to
+    /// play.
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`Rules::score`].
+    pub fn move_score(&self, rules: &Rules) -> Result<usize, &'static str> {
+        rules.score(self.opponent, self.response)
+    }
+
+    /// Returns the score of the round when the second column is the desired
+    /// outcome.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the second column isn't a goal or no move reaches it, or like
+    /// [`Rules::score`].
+    pub fn goal_score(&self, rules: &Rules) -> Result<usize, &'static str> {
+        let goal = self
+            .goal
+            .ok_or("Second column isn't a goal on every line")?;
+        let y = rules
+            .move_for(self.opponent, goal)
+            .ok_or("No move reaches the goal of a round")?;
+        rules.score(self.opponent, y)
+    }
+}
+
+/// Total scores of a strategy guide under both readings of its second column,
+/// or why a round can't be scored that way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scores {
+    /// Total when the second column is the shape to play.
+    pub by_move: Result<usize, &'static str>,
+    /// Total when the second column is how the round should end.
+    pub by_goal: Result<usize, &'static str>,
+}
+
+impl Scores {
+    /// Adds the scores of `round` under both readings, keeping the first
+    /// error of each.
+    fn add(&mut self, rules: &Rules, round: &Round) {
+        let add = |total: Result<usize, _>, score: Result<usize, _>| {
+            total?.checked_add(score?).ok_or("Total score overflows")
+        };
+        self.by_move = add(self.by_move, round.move_score(rules));
+        self.by_goal = self
+            .by_goal
+            .and_then(|total| add(Ok(total), round.goal_score(rules)));
+    }
+}
+
+/// Scores the strategy guide of the puzzle, rock paper scissors, one round
+/// per line without keeping the rounds.
+///
+/// # Errors
+///
+/// Fails like [`score_guide_with`].
+pub fn score_guide(input: impl BufRead) -> Result<Scores, ParseError> {
+    score_guide_with(&Rules::default(), input)
+}
+
+/// Scores a strategy guide for the game of `rules`, one round per line
+/// without keeping the rounds.
+///
+/// # Errors
+///
+/// Fails on the first line that isn't two valid symbols separated by a
+/// space. Rounds that can't be scored under one of the readings, or totals
+/// that overflow, are reported by the [`Scores`] of that reading instead.
+pub fn score_guide_with(rules: &Rules, input: impl BufRead) -> Result<Scores, ParseError> {
+    let mut scores = Scores {
+        by_move: Ok(0),
+        by_goal: Ok(0),
+    };
+    for_each_line(input, |line| {
+        scores.add(rules, &Round::parse(rules, line)?);
+        Ok(())
+    })?;
+    Ok(scores)
+}
+
+/// The solution: the total score under both readings of the guide.
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const SOLVED_WHILE_PARSING: bool = true;
+    type Input = Scores;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Self::parse_reader(input.as_bytes())
+    }
+
+    fn parse_reader(input: impl BufRead) -> aoc::Result<Self::Input> {
+        Ok(score_guide(input)?)
+    }
+
+    fn part_a(scores: &Self::Input) -> aoc::Result<Answer> {
+        Ok(scores.by_move?.into())
+    }
+
+    fn part_b(scores: &Self::Input) -> aoc::Result<Answer> {
+        Ok(scores.by_goal?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::ROCK_PAPER_SCISSORS_LIZARD_SPOCK;
+    use aoc::Part;
+
+    #[test]
+    fn sample_scores() {
+        let scores = score_guide(include_str!("../sample.txt").as_bytes()).unwrap();
+        assert_eq!(
+            scores,
+            Scores {
+                by_move: Ok(15),
+                by_goal: Ok(12)
+            }
+        );
+        let err = score_guide("A Y\nB W".as_bytes()).unwrap_err();
+        assert_eq!(err.position(), Some((2, 3)));
+    }
+
+    #[test]
+    fn lizard_spock_guide() {
+        let rules = Rules::parse(ROCK_PAPER_SCISSORS_LIZARD_SPOCK).unwrap();
+        // Rock against paper wins, lizard against spock too, draw with scissors.
+        let scores = score_guide_with(&rules, "A X\nB Y\nE Z".as_bytes()).unwrap();
+        assert_eq!(scores.by_move, Ok(9 + 10 + 8));
+        // Draw with paper, win against spock with lizard (4), not paper.
+        let guide = "C Y\nB Z";
+        let scores = score_guide_with(&rules, guide.as_bytes()).unwrap();
+        assert_eq!(scores.by_goal, Ok(6 + 10));
+        let scores = score_guide_with(&rules, "A V".as_bytes()).unwrap();
+        assert!(scores.by_goal.is_err());
+    }
+
+    #[test]
+    fn overflowing_scores() {
+        let big = usize::MAX / 2;
+        let rules =
+            format!("move a A X {big}\nmove b B Y 0\nbeats a b\ngoal X win 1\ngoal Y lose 0");
+        let rules = Rules::parse(&rules).unwrap();
+        let scores = score_guide_with(&rules, "B X".as_bytes()).unwrap();
+        assert_eq!((scores.by_move, scores.by_goal), (Ok(big + 1), Ok(big + 1)));
+        let scores = score_guide_with(&rules, "B X\nB X\nB X".as_bytes()).unwrap();
+        let overflow = Err("Total score overflows");
+        assert_eq!((scores.by_move, scores.by_goal), (overflow, overflow));
+    }
+
+    #[test]
+    fn scoring_errors_when_solving() {
+        // No points for losing, which only the moves can do.
+        let rules = "move a A X 1\nmove b B Y 2\nbeats a b\ngoal X win 1\ngoal Y draw 0";
+        let rules = Rules::parse(rules).unwrap();
+        let scores = score_guide_with(&rules, "B X\nA Y".as_bytes()).unwrap();
+        let no_points = "No points for the outcome of the round";
+        assert_eq!(
+            (scores.by_move, scores.by_goal),
+            (Err(no_points), Ok(2 + 1))
+        );
+        let err = Day02::solve(&scores, Part::A).unwrap_err();
+        assert_eq!(err.to_string(), no_points);
+    }
+}
@@ -1,116 +1,36 @@
-#![warn(clippy::pedantic)]
-
-use std::error::Error;
-use std::fs;
-use std::str::FromStr;
-
-#[derive(Clone, Copy)]
-enum Move {
-    Rock,
-    Paper,
-    Scissors,
-}
-
-#[derive(Clone, Copy)]
-enum Goal {
-    Loose,
-    Draw,
-    Win,
-}
-
-impl FromStr for Move {
-    type Err = &'static str;
-
-    fn from_str(input: &str) -> Result<Move, Self::Err> {
-        match input {
-            "A" => Ok(Move::Rock),
-            "B" => Ok(Move::Paper),
-            "C" => Ok(Move::Scissors),
-            "X" => Ok(Move::Rock),
-            "Y" => Ok(Move::Paper),
-            "Z" => Ok(Move::Scissors),
-            _ => Err("Invalid move"),
-        }
-    }
-}
-
-impl FromStr for Goal {
-    type Err = &'static str;
-
-    fn from_str(input: &str) -> Result<Goal, Self::Err> {
-        match input {
-            "X" => Ok(Goal::Loose),
-            "Y" => Ok(Goal::Draw),
-            "Z" => Ok(Goal::Win),
-            _ => Err("Invalid goal"),
-        }
-    }
-}
-
-fn calc_winloss_score(x: Move, y: Move) -> usize {
-    match (x, y) {
-        (Move::Rock, Move::Rock) => 3,
-        (Move::Paper, Move::Paper) => 3,
-        (Move::Scissors, Move::Scissors) => 3,
-        (Move::Rock, Move::Paper) => 6,
-        (Move::Paper, Move::Rock) => 0,
-        (Move::Rock, Move::Scissors) => 0,
-        (Move::Scissors, Move::Rock) => 6,
-        (Move::Paper, Move::Scissors) => 6,
-        (Move::Scissors, Move::Paper) => 0,
-    }
-}
-
-fn calc_score(x: Move) -> usize {
-    match x {
-        Move::Rock => 1,
-        Move::Paper => 2,
-        Move::Scissors => 3,
-    }
-}
-
-fn get_winning_move(x: Move) -> Move {
-    match x {
-        Move::Rock => Move::Paper,
-        Move::Paper => Move::Scissors,
-        Move::Scissors => Move::Rock,
-    }
-}
-
-fn get_loosing_move(x: Move) -> Move {
-    match x {
-        Move::Rock => Move::Scissors,
-        Move::Paper => Move::Rock,
-        Move::Scissors => Move::Paper,
-    }
-}
-
-fn get_move(x: Move, goal: Goal) -> Move {
-    match goal {
-        Goal::Win => get_winning_move(x),
-        Goal::Draw => x,
-        Goal::Loose => get_loosing_move(x),
+use aoc::{InputArgs, Part, Solution};
+use clap::Parser;
+use day02::{score_guide_with, Day02, Rules};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(about = "Solves both parts of the day's puzzle")]
+struct Args {
+    #[command(flatten)]
+    input: InputArgs,
+    /// Play the game defined in FILE instead of rock paper scissors, e.g.
+    /// rules/rpsls.txt
+    #[arg(long, value_name = "FILE")]
+    rules: Option<PathBuf>,
+}
+
+/// Solves both parts like [`aoc::run`], with the rules in `rules`.
+fn run_with(path: &Path, rules: &Path) -> aoc::Result<()> {
+    let rules =
+        Rules::parse(&aoc::read_input(rules)?).map_err(|e| format!("{}: {e}", rules.display()))?;
+    let scores = score_guide_with(&rules, aoc::open_input(path)?)?;
+    for part in Part::ALL {
+        aoc::print_answer(part, &Day02::solve(&scores, part)?);
     }
+    Ok(())
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let (score, score2) = fs::read_to_string("input.txt")?
-        .lines()
-        .map(|line| {
-            let moves = line.split(" ").collect::<Vec<&str>>();
-            let x = Move::from_str(moves[0]).unwrap();
-            let y = Move::from_str(moves[1]).unwrap();
-            let y2 = get_move(x, Goal::from_str(moves[1]).unwrap());
-            (
-                calc_score(y) + calc_winloss_score(x, y),
-                calc_score(y2) + calc_winloss_score(x, y2),
-            )
-        })
-        .collect::<Vec<_>>()
-        .iter()
-        .fold((0, 0), |a, b| (a.0 + b.0, a.1 + b.1));
-    println!("A: {score}");
-    println!("B: {score2}");
-
-    Ok(())
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let path = args.input.resolve(Path::new(env!("CARGO_MANIFEST_DIR")));
+    aoc::exit(match args.rules {
+        Some(rules) => run_with(&path, &rules),
+        None => aoc::run::<Day02>(path),
+    })
 }
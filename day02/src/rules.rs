@@ -0,0 +1,383 @@
+//! The rules of a game like rock paper scissors: its moves, which move beats
+//! which, and the symbols of the strategy guide, loaded from a definition.
+
+use aoc::{parse, ParseError};
+use std::str::FromStr;
+
+/// The rules of the puzzle, rock paper scissors.
+pub const ROCK_PAPER_SCISSORS: &str = include_str!("../rules/rps.txt");
+
+/// Rock paper scissors lizard Spock.
+pub const ROCK_PAPER_SCISSORS_LIZARD_SPOCK: &str = include_str!("../rules/rpsls.txt");
+
+/// A move of a game, the index of its `move` line in the rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Move(pub usize);
+
+/// How a round ends for the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Goal {
+    /// The opponent's move beats the player's.
+    Lose,
+    /// Both play the same move.
+    Draw,
+    /// The player's move beats the opponent's.
+    Win,
+}
+
+impl FromStr for Goal {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Goal, Self::Err> {
+        match input {
+            "lose" => Ok(Goal::Lose),
+            "draw" => Ok(Goal::Draw),
+            "win" => Ok(Goal::Win),
+            _ => Err(ParseError::line(
+                format!("expected `lose`, `draw` or `win`, not `{input}`"),
+                input,
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MoveRule {
+    name: String,
+    opponent: String,
+    response: String,
+    score: usize,
+}
+
+#[derive(Debug, Clone)]
+struct GoalRule {
+    symbol: String,
+    goal: Goal,
+    points: usize,
+}
+
+/// The rules of a game, read from a definition with one rule per line:
+///
+/// ```text
+/// move <name> <opponent symbol> <response symbol> <score>
+/// goal <symbol> <lose|draw|win> <points>
+/// beats <name> <name>...
+/// ```
+///
+/// A round scores the score of the player's move plus the points of its
+/// outcome. `beats` lines list the moves the first one beats; without any,
+/// each move beats the `(n - 1) / 2` moves listed before it, wrapping around,
+/// which makes any odd number of moves a fair cyclic game. Blank lines and
+/// lines starting with `#` are ignored.
+#[derive(Debug, Clone)]
+pub struct Rules {
+    moves: Vec<MoveRule>,
+    goals: Vec<GoalRule>,
+    /// Whether move `a` beats move `b`, at `a * moves.len() + b`.
+    beats: Vec<bool>,
+}
+
+/// Parses one line of the rules, adding `move` and `goal` rules to `moves`
+/// and `goals`. Returns whether it is a `beats` line, which are read once all
+/// moves are known.
+fn parse_rule(
+    line: &str,
+    moves: &mut Vec<MoveRule>,
+    goals: &mut Vec<GoalRule>,
+) -> Result<bool, ParseError> {
+    let err = |message: String, span: &str| Err(ParseError::new(message, line, span));
+    match line.split_whitespace().collect::<Vec<_>>()[..] {
+        [] => Ok(false),
+        [comment, ..] if comment.starts_with('#') => Ok(false),
+        ["move", name, opponent, response, score] => {
+            let score = parse::token(line, score)?;
+            if moves.iter().any(|m| m.name == name) {
+                return err(format!("move `{name}` defined twice"), name);
+            } else if moves.iter().any(|m| m.opponent == opponent) {
+                return err(format!("symbol `{opponent}` used twice"), opponent);
+            } else if moves.iter().any(|m| m.response == response) {
+                return err(format!("symbol `{response}` used twice"), response);
+            }
+            moves.push(MoveRule {
+                name: name.to_string(),
+                opponent: opponent.to_string(),
+                response: response.to_string(),
+                score,
+            });
+            Ok(false)
+        }
+        ["goal", symbol, goal, points] => {
+            let goal = goal.parse().map_err(|e: ParseError| e.within(line, goal))?;
+            let points = parse::token(line, points)?;
+            if goals.iter().any(|g| g.symbol == symbol) {
+                return err(format!("symbol `{symbol}` used twice"), symbol);
+            } else if goals.iter().any(|g| g.goal == goal) {
+                return err("goal defined twice".to_string(), line);
+            }
+            goals.push(GoalRule {
+                symbol: symbol.to_string(),
+                goal,
+                points,
+            });
+            Ok(false)
+        }
+        ["beats", _, _, ..] => Ok(true),
+        _ => Err(ParseError::line(
+            "expected `move <name> <opponent> <response> <score>`, \
+             `goal <symbol> <lose|draw|win> <points>` or `beats <name> <name>...`",
+            line,
+        )),
+    }
+}
+
+impl Default for Rules {
+    /// Returns the rules of rock paper scissors.
+    fn default() -> Rules {
+        Rules::parse(ROCK_PAPER_SCISSORS).expect("the rules of the puzzle are valid")
+    }
+}
+
+impl Rules {
+    /// Parses and checks a definition of the rules.
+    ///
+    /// # Errors
+    ///
+    /// Fails on malformed lines, names or symbols used twice, unknown names
+    /// in `beats` lines, and unless every two different moves are decided by
+    /// exactly one of them beating the other.
+    pub fn parse(input: &str) -> Result<Rules, ParseError> {
+        let mut moves = Vec::<MoveRule>::new();
+        let mut goals = Vec::<GoalRule>::new();
+        let mut beats_lines = vec![];
+
+        for (i, line) in input.lines().enumerate() {
+            let is_beats =
+                parse_rule(line, &mut moves, &mut goals).map_err(|e| e.shift_lines(i))?;
+            if is_beats {
+                beats_lines.push((i, line));
+            }
+        }
+
+        let n = moves.len();
+        if n == 0 {
+            return Err(ParseError::input("no moves defined"));
+        }
+        let mut beats = vec![false; n * n];
+        if beats_lines.is_empty() {
+            for a in 0..n {
+                for d in 1..=(n - 1) / 2 {
+                    beats[a * n + (a + n - d) % n] = true;
+                }
+            }
+        }
+        for (i, line) in beats_lines {
+            let find = |name: &str| {
+                moves.iter().position(|m| m.name == name).ok_or_else(|| {
+                    ParseError::new(format!("unknown move `{name}`"), line, name).shift_lines(i)
+                })
+            };
+            let mut names = line.split_whitespace().skip(1);
+            let winner = find(names.next().unwrap_or_default())?;
+            for loser in names {
+                beats[winner * n + find(loser)?] = true;
+            }
+        }
+
+        for a in 0..n {
+            for b in a..n {
+                let (ab, ba) = (beats[a * n + b], beats[b * n + a]);
+                let (x, y) = (&moves[a].name, &moves[b].name);
+                if a == b && ab {
+                    return Err(ParseError::input(format!("move `{x}` beats itself")));
+                }
+                if a != b && ab == ba {
+                    let message = if ab {
+                        "beat each other"
+                    } else {
+                        "don't beat each other"
+                    };
+                    return Err(ParseError::input(format!(
+                        "moves `{x}` and `{y}` {message}"
+                    )));
+                }
+            }
+        }
+
+        Ok(Rules {
+            moves,
+            goals,
+            beats,
+        })
+    }
+
+    /// Returns every move, in the order of the rules.
+    pub fn moves(&self) -> impl DoubleEndedIterator<Item = Move> + use<> {
+        (0..self.moves.len()).map(Move)
+    }
+
+    /// Returns the name of `m`.
+    #[must_use]
+    pub fn name(&self, m: Move) -> &str {
+        &self.moves[m.0].name
+    }
+
+    /// Returns the move of the opponent's `symbol`.
+    #[must_use]
+    pub fn opponent_move(&self, symbol: &str) -> Option<Move> {
+        self.moves
+            .iter()
+            .position(|m| m.opponent == symbol)
+            .map(Move)
+    }
+
+    /// Returns the move of the player's `symbol`.
+    #[must_use]
+    pub fn response_move(&self, symbol: &str) -> Option<Move> {
+        self.moves
+            .iter()
+            .position(|m| m.response == symbol)
+            .map(Move)
+    }
+
+    /// Returns the goal of `symbol`.
+    #[must_use]
+    pub fn goal(&self, symbol: &str) -> Option<Goal> {
+        self.goals
+            .iter()
+            .find(|g| g.symbol == symbol)
+            .map(|g| g.goal)
+    }
+
+    /// Returns whether `a` beats `b`.
+    #[must_use]
+    pub fn beats(&self, a: Move, b: Move) -> bool {
+        self.beats[a.0 * self.moves.len() + b.0]
+    }
+
+    /// Returns how the round ends for the player playing `response`.
+    #[must_use]
+    pub fn outcome(&self, opponent: Move, response: Move) -> Goal {
+        if self.beats(response, opponent) {
+            Goal::Win
+        } else if self.beats(opponent, response) {
+            Goal::Lose
+        } else {
+            Goal::Draw
+        }
+    }
+
+    /// Returns the score of the player playing `response`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the rules give no points for the outcome or the score
+    /// overflows.
+    pub fn score(&self, opponent: Move, response: Move) -> Result<usize, &'static str> {
+        let goal = self.outcome(opponent, response);
+        let goal = self.goals.iter().find(|g| g.goal == goal);
+        let points = goal.ok_or("No points for the outcome of the round")?.points;
+        self.moves[response.0]
+            .score
+            .checked_add(points)
+            .ok_or("Score of the round overflows")
+    }
+
+    /// Returns the highest scoring move that reaches `goal` against
+    /// `opponent`, the first one listed on ties.
+    #[must_use]
+    pub fn move_for(&self, opponent: Move, goal: Goal) -> Option<Move> {
+        self.moves()
+            .filter(|&m| self.outcome(opponent, m) == goal)
+            .rev()
+            .max_by_key(|m| self.moves[m.0].score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rock_paper_scissors() {
+        let rules = Rules::default();
+        let [rock, paper, scissors] = ["rock", "paper", "scissors"]
+            .map(|name| rules.moves().find(|&m| rules.name(m) == name).unwrap());
+        assert!(rules.beats(paper, rock) && rules.beats(scissors, paper));
+        assert!(rules.beats(rock, scissors) && !rules.beats(rock, paper));
+        assert_eq!(rules.opponent_move("B"), Some(paper));
+        assert_eq!(rules.response_move("Z"), Some(scissors));
+        assert_eq!(rules.goal("X"), Some(Goal::Lose));
+        assert_eq!(rules.score(rock, paper), Ok(8));
+        assert_eq!(rules.score(paper, rock), Ok(1));
+        assert_eq!(rules.score(scissors, scissors), Ok(6));
+        assert_eq!(rules.move_for(rock, Goal::Win), Some(paper));
+    }
+
+    #[test]
+    fn lizard_spock() {
+        let rules = Rules::parse(ROCK_PAPER_SCISSORS_LIZARD_SPOCK).unwrap();
+        let find = |name| rules.moves().find(|&m| rules.name(m) == name).unwrap();
+        let beaten = |name| {
+            let mut names = rules
+                .moves()
+                .filter(|&m| rules.beats(find(name), m))
+                .map(|m| rules.name(m))
+                .collect::<Vec<_>>();
+            names.sort_unstable();
+            names
+        };
+        assert_eq!(beaten("rock"), ["lizard", "scissors"]);
+        assert_eq!(beaten("spock"), ["rock", "scissors"]);
+        assert_eq!(beaten("lizard"), ["paper", "spock"]);
+        // Both spock and paper beat rock, paper scores more.
+        assert_eq!(rules.move_for(find("rock"), Goal::Win), Some(find("paper")));
+    }
+
+    #[test]
+    fn explicit_beats() {
+        let rules = "move a A X 1\nmove b B Y 2\nmove c C Z 3\nbeats a b c\nbeats b c";
+        let rules = Rules::parse(rules).unwrap();
+        assert!(rules.beats(Move(0), Move(2)) && rules.beats(Move(1), Move(2)));
+        assert_eq!(rules.outcome(Move(2), Move(0)), Goal::Win);
+        // No points for outcomes without a goal.
+        assert!(rules.score(Move(0), Move(1)).is_err());
+
+        let rules = format!(
+            "move a A X {}\nmove b B Y 0\nbeats a b\ngoal W win 1",
+            usize::MAX
+        );
+        let rules = Rules::parse(&rules).unwrap();
+        assert_eq!(
+            rules.score(Move(1), Move(0)),
+            Err("Score of the round overflows")
+        );
+    }
+
+    #[test]
+    fn invalid_rules() {
+        let err = |rules| Rules::parse(rules).unwrap_err();
+        assert_eq!(
+            err("# x\nmove a A X 1\nmove a B Y 2").position(),
+            Some((3, 6))
+        );
+        assert_eq!(
+            err("move a A X 1\nmove b B Y two").position(),
+            Some((2, 12))
+        );
+        assert_eq!(err("goal X loose 0").position(), Some((1, 8)));
+        assert_eq!(err("move a A X 1\nbeats a b").position(), Some((2, 9)));
+        assert_eq!(err("").message(), "no moves defined");
+        assert_eq!(
+            err("move a A X 1\nmove b B Y 2\nmove c C Z 3\nmove d D W 4").message(),
+            "moves `a` and `c` don't beat each other"
+        );
+        assert_eq!(
+            err("move a A X 1\nmove b B Y 2\nbeats a b\nbeats b a").message(),
+            "moves `a` and `b` beat each other"
+        );
+        assert_eq!(
+            err("move a A X 1\nmove b B Y 2\nbeats a a b").message(),
+            "move `a` beats itself"
+        );
+    }
+}
@@ -0,0 +1,170 @@
+#![warn(missing_docs)]
+
+//! Day 3: Rucksack Reorganization, priorities of misplaced items and badges.
+
+use aoc::stream::for_each_line;
+use aoc::{Answer, ParseError, Solution};
+use std::collections::HashSet;
+use std::io::BufRead;
+
+/// Returns the priority of an item, 1 to 26 for `a` to `z` and 27 to 52 for
+/// `A` to `Z`, or `None` if it isn't an item.
+#[must_use]
+pub fn priority(item: char) -> Option<usize> {
+    match item {
+        'a'..='z' => Some(item as usize - 'a' as usize + 1),
+        'A'..='Z' => Some(item as usize - 'A' as usize + 27),
+        _ => None,
+    }
+}
+
+/// Checks that a rucksack holds only valid items, evenly split between its
+/// two compartments.
+fn parse_rucksack(line: &str) -> Result<String, ParseError> {
+    if let Some((i, c)) = line.char_indices().find(|&(_, c)| priority(c).is_none()) {
+        return Err(ParseError::new(
+            format!("invalid item `{c}`"),
+            line,
+            &line[i..i + c.len_utf8()],
+        ));
+    }
+    if !line.len().is_multiple_of(2) {
+        return Err(ParseError::line("odd number of items", line));
+    }
+    Ok(line.to_string())
+}
+
+fn items(rucksack: &str) -> HashSet<char> {
+    rucksack.chars().collect()
+}
+
+/// Returns the priority of the items found in both compartments of a
+/// rucksack. Characters that aren't items are ignored.
+///
+/// # Panics
+///
+/// Panics if the middle of the rucksack isn't a character boundary, which
+/// can't happen for rucksacks of items checked to be of even length.
+#[must_use]
+pub fn compartment_priority(rucksack: &str) -> usize {
+    let (left, right) = rucksack.split_at(rucksack.len() / 2);
+    items(left)
+        .intersection(&items(right))
+        .filter_map(|&c| priority(c))
+        .sum()
+}
+
+/// Returns the priority of the badge shared by the rucksacks of `group` and
+/// the `last` rucksack completing it.
+///
+/// # Errors
+///
+/// Fails, pointing at `last`, unless the group shares exactly one item.
+pub fn badge_priority(group: &[String], last: &str) -> Result<usize, ParseError> {
+    let mut shared = items(last);
+    for rucksack in group {
+        shared.retain(|c| rucksack.contains(*c));
+    }
+    match shared.iter().collect::<Vec<_>>()[..] {
+        [&badge] => priority(badge)
+            .ok_or_else(|| ParseError::line(format!("invalid badge `{badge}`"), last)),
+        ref badges => Err(ParseError::line(
+            format!(
+                "the group shares {} item types, expected a single badge",
+                badges.len()
+            ),
+            last,
+        )),
+    }
+}
+
+/// Sums of the item priorities the two parts ask for.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Priorities {
+    /// Items found in both compartments of each rucksack.
+    pub compartments: usize,
+    /// Badges shared by each group of three elves.
+    pub badges: usize,
+}
+
+/// Sums the priorities one rucksack per line, keeping only the current group
+/// of three elves in memory. An incomplete last group is ignored.
+///
+/// # Errors
+///
+/// Fails on the first rucksack with an invalid item or an odd number of
+/// items, or that completes a group without exactly one common item.
+pub fn sum_priorities(input: impl BufRead) -> Result<Priorities, ParseError> {
+    let mut sums = Priorities::default();
+    let mut group = Vec::with_capacity(2);
+    for_each_line(input, |line| {
+        let rucksack = parse_rucksack(line)?;
+        sums.compartments += compartment_priority(&rucksack);
+        if group.len() == 2 {
+            sums.badges += badge_priority(&group, &rucksack)?;
+            group.clear();
+        } else {
+            group.push(rucksack);
+        }
+        Ok(())
+    })?;
+    Ok(sums)
+}
+
+/// The solution: the priority sums of misplaced items and of badges.
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const SOLVED_WHILE_PARSING: bool = true;
+    type Input = Priorities;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Self::parse_reader(input.as_bytes())
+    }
+
+    fn parse_reader(input: impl BufRead) -> aoc::Result<Self::Input> {
+        Ok(sum_priorities(input)?)
+    }
+
+    fn part_a(sums: &Self::Input) -> aoc::Result<Answer> {
+        Ok(sums.compartments.into())
+    }
+
+    fn part_b(sums: &Self::Input) -> aoc::Result<Answer> {
+        Ok(sums.badges.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw";
+
+    #[test]
+    fn example() {
+        let sums = sum_priorities(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            sums,
+            Priorities {
+                compartments: 157,
+                badges: 70
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_items() {
+        let err = sum_priorities("abcd\nab1d".as_bytes()).unwrap_err();
+        assert_eq!(err.position(), Some((2, 3)));
+        assert!(sum_priorities("abc".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn group_without_badge() {
+        let err = sum_priorities("ab\nab\ncd".as_bytes()).unwrap_err();
+        assert_eq!(err.position(), Some((3, 1)));
+        assert!(sum_priorities("ab\nab\nab".as_bytes()).is_err());
+    }
+}
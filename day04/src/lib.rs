@@ -0,0 +1,108 @@
+#![warn(clippy::pedantic)]
+#![warn(missing_docs)]
+
+//! Day 4: Camp Cleanup, overlapping section assignments.
+
+use aoc::stream::for_each_line;
+use aoc::{parse, Answer, ParseError, Solution};
+use itertools::Itertools;
+use std::io::BufRead;
+
+/// Comparisons of inclusive ranges.
+pub trait Interval<Rhs = Self> {
+    /// Whether `rhs` lies completely within `self`.
+    fn contains(self, rhs: Rhs) -> bool;
+    /// Whether `self` and `rhs` share at least one value.
+    fn overlaps(self, rhs: Rhs) -> bool;
+}
+
+impl Interval for (u32, u32) {
+    fn contains(self, other: (u32, u32)) -> bool {
+        self.0 <= other.0 && self.1 >= other.1
+    }
+
+    fn overlaps(self, other: (u32, u32)) -> bool {
+        self.0 <= other.1 && other.0 <= self.1
+    }
+}
+
+/// Section assignment of a single elf, as an inclusive range.
+pub type Assignment = (u32, u32);
+
+/// Parses a line of two `a-b` assignments separated by a comma.
+///
+/// # Errors
+///
+/// Fails if the line isn't four numbers in that shape.
+pub fn parse_pair(line: &str) -> Result<(Assignment, Assignment), ParseError> {
+    let (a, b, c, d) = line
+        .split(['-', ','])
+        .map(|i| parse::token::<u32>(line, i))
+        .collect_tuple::<(_, _, _, _)>()
+        .ok_or_else(|| ParseError::line("expected a pair of `a-b` section ranges", line))?;
+    Ok(((a?, b?), (c?, d?)))
+}
+
+/// Counts of the pairs the two parts ask for.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Overlaps {
+    /// Pairs where one assignment fully contains the other.
+    pub contained: usize,
+    /// Pairs whose assignments overlap at all.
+    pub overlapping: usize,
+}
+
+/// Counts the overlapping pairs, one pair of assignments per line.
+///
+/// # Errors
+///
+/// Fails on the first line that isn't a pair of `a-b` section ranges.
+pub fn count_overlaps(input: impl BufRead) -> Result<Overlaps, ParseError> {
+    let mut counts = Overlaps::default();
+    for_each_line(input, |line| {
+        let (x, y) = parse_pair(line)?;
+        counts.contained += usize::from(x.contains(y) || y.contains(x));
+        counts.overlapping += usize::from(x.overlaps(y));
+        Ok(())
+    })?;
+    Ok(counts)
+}
+
+/// The solution: the pairs where one assignment contains the other, then
+/// the pairs that overlap at all.
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const SOLVED_WHILE_PARSING: bool = true;
+    type Input = Overlaps;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Self::parse_reader(input.as_bytes())
+    }
+
+    fn parse_reader(input: impl BufRead) -> aoc::Result<Self::Input> {
+        Ok(count_overlaps(input)?)
+    }
+
+    fn part_a(counts: &Self::Input) -> aoc::Result<Answer> {
+        Ok(counts.contained.into())
+    }
+
+    fn part_b(counts: &Self::Input) -> aoc::Result<Answer> {
+        Ok(counts.overlapping.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_pairs() {
+        let err = count_overlaps("2-4,6-8\n2-3,x-5".as_bytes()).unwrap_err();
+        assert_eq!(err.position(), Some((2, 5)));
+        assert!(count_overlaps("2-4,6".as_bytes()).is_err());
+        assert!(count_overlaps("2-4,6-8-9".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,422 @@
+#![warn(clippy::pedantic)]
+#![warn(missing_docs)]
+
+//! Day 5: Supply Stacks, a crane rearranging stacks of crates.
+
+use aoc::parse::{self, blocks};
+use aoc::visual::{Frame, Visualize};
+use aoc::{Answer, ParseError, Solution};
+use itertools::Itertools;
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+
+/// The stacks of crates, numbered from left to right, each with its top
+/// crate first.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Stacks {
+    stacks: Vec<VecDeque<char>>,
+}
+
+impl Clone for Stacks {
+    fn clone(&self) -> Stacks {
+        Stacks {
+            stacks: self
+                .stacks
+                .iter()
+                .map(|s| s.iter().copied().collect::<VecDeque<char>>())
+                .collect(),
+        }
+    }
+}
+
+impl Stacks {
+    /// Creates stacks from their crates, each listed from the top down.
+    #[must_use]
+    pub fn new(stacks: Vec<VecDeque<char>>) -> Stacks {
+        Stacks { stacks }
+    }
+
+    /// Returns the crates of every stack, each from the top down.
+    #[must_use]
+    pub fn stacks(&self) -> &[VecDeque<char>] {
+        &self.stacks
+    }
+
+    /// Returns the top crate of every non-empty stack.
+    #[must_use]
+    pub fn get_top_items(&self) -> String {
+        self.stacks.iter().filter_map(VecDeque::front).collect()
+    }
+
+    /// Takes the top `m.count` crates off the source stack of `m`, top crate
+    /// first, after checking that both stacks of the move exist.
+    fn take(&mut self, m: &Move) -> Result<Vec<char>, &'static str> {
+        if m.from.max(m.to) >= self.stacks.len() {
+            return Err("Move between stacks that don't exist");
+        }
+        let from = &mut self.stacks[m.from];
+        if from.len() < m.count {
+            return Err("Move of more crates than the stack holds");
+        }
+        Ok(from.drain(..m.count).collect())
+    }
+
+    /// Applies `m` with a crane that moves one crate at a time.
+    ///
+    /// # Errors
+    ///
+    /// Fails, leaving the stacks unchanged, if a stack of the move doesn't
+    /// exist or holds fewer crates than are moved.
+    pub fn move_single(&mut self, m: &Move) -> Result<(), &'static str> {
+        for item in self.take(m)? {
+            self.stacks[m.to].push_front(item);
+        }
+        Ok(())
+    }
+
+    /// Applies `m` with a crane that moves several crates at once.
+    ///
+    /// # Errors
+    ///
+    /// Fails, leaving the stacks unchanged, if a stack of the move doesn't
+    /// exist or holds fewer crates than are moved.
+    pub fn move_multiple(&mut self, m: &Move) -> Result<(), &'static str> {
+        for item in self.take(m)?.into_iter().rev() {
+            self.stacks[m.to].push_front(item);
+        }
+        Ok(())
+    }
+}
+
+/// A step of the rearrangement procedure.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Move {
+    /// Index of the stack the crates are taken from, counting from 0.
+    pub from: usize,
+    /// Index of the stack the crates are put on, counting from 0.
+    pub to: usize,
+    /// Number of crates moved.
+    pub count: usize,
+}
+
+impl FromStr for Stacks {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Stacks, Self::Err> {
+        let lines = input.lines().collect::<Vec<_>>();
+        let (numbers, crates) = lines
+            .split_last()
+            .ok_or_else(|| ParseError::input("missing drawing of the initial stacks"))?;
+        let mut stacks = numbers
+            .split_whitespace()
+            .map(|_| VecDeque::<char>::new())
+            .collect::<Vec<_>>();
+
+        for (i, line) in crates.iter().enumerate().rev() {
+            let items = line
+                .char_indices()
+                .skip(1)
+                .step_by(4)
+                .enumerate()
+                .filter(|(_, (_, c))| *c != ' ');
+            for (idx, (pos, item)) in items {
+                stacks
+                    .get_mut(idx)
+                    .ok_or_else(|| {
+                        let span = &line[pos..pos + item.len_utf8()];
+                        ParseError::new("crate outside of the numbered stacks", line, span)
+                            .shift_lines(i)
+                    })?
+                    .push_front(item);
+            }
+        }
+        Ok(Stacks { stacks })
+    }
+}
+
+impl fmt::Display for Stacks {
+    /// Draws the stacks like the puzzle input, with the stack numbers below.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let height = self.stacks.iter().map(VecDeque::len).max().unwrap_or(0);
+        for row in (1..=height).rev() {
+            let line = self
+                .stacks
+                .iter()
+                .map(|stack| match stack.len().checked_sub(row) {
+                    Some(i) => format!("[{}]", stack[i]),
+                    None => "   ".to_string(),
+                })
+                .join(" ");
+            writeln!(f, "{line}")?;
+        }
+        let numbers = (1..=self.stacks.len()).map(|i| format!(" {i} ")).join(" ");
+        write!(f, "{numbers}")
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.count,
+            self.from + 1,
+            self.to + 1
+        )
+    }
+}
+
+/// Parses a 1-based stack number into an index.
+fn parse_stack(line: &str, token: &str) -> Result<usize, ParseError> {
+    parse::token::<usize>(line, token)?
+        .checked_sub(1)
+        .ok_or_else(|| ParseError::new("stacks are numbered from 1", line, token))
+}
+
+impl FromStr for Move {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Move, Self::Err> {
+        let (count, from, to) = input
+            .split([' '])
+            .tuples::<(_, _)>()
+            .map(|(_, i)| i)
+            .collect_tuple::<(_, _, _)>()
+            .ok_or_else(|| ParseError::line("expected `move <n> from <a> to <b>`", input))?;
+        Ok(Move {
+            from: parse_stack(input, from)?,
+            to: parse_stack(input, to)?,
+            count: parse::token(input, count)?,
+        })
+    }
+}
+
+/// Splits the puzzle input into the initial stacks and the list of moves.
+///
+/// # Errors
+///
+/// Fails if the drawing or any of the moves can't be parsed, or if a move
+/// refers to a stack that isn't in the drawing.
+pub fn parse_input(data: &str) -> Result<(Stacks, Vec<Move>), ParseError> {
+    let ((_, input_stacks), (moves_start, input_moves)) =
+        blocks(data).collect_tuple::<(_, _)>().ok_or_else(|| {
+            ParseError::input("expected the initial stacks and the moves separated by a blank line")
+        })?;
+
+    let stacks = Stacks::from_str(input_stacks)?;
+    let n_stacks = stacks.stacks.len();
+    let moves = parse::lines(input_moves, |line| {
+        let m = Move::from_str(line)?;
+        match [m.from, m.to].into_iter().find(|&i| i >= n_stacks) {
+            Some(i) => Err(ParseError::line(
+                format!("there is no stack {}, only {n_stacks}", i + 1),
+                line,
+            )),
+            None => Ok(m),
+        }
+    })
+    .map_err(|e| e.shift_lines(moves_start))?;
+    Ok((stacks, moves))
+}
+
+/// Applies `moves` with a crane that moves one crate at a time.
+///
+/// # Errors
+///
+/// Fails if a move takes more crates than its stack holds.
+pub fn rearrange_single(stacks: &mut Stacks, moves: &[Move]) -> Result<(), &'static str> {
+    moves.iter().try_for_each(|m| stacks.move_single(m))
+}
+
+/// Applies `moves` with a crane that moves several crates at once.
+///
+/// # Errors
+///
+/// Fails if a move takes more crates than its stack holds.
+pub fn rearrange_multiple(stacks: &mut Stacks, moves: &[Move]) -> Result<(), &'static str> {
+    moves.iter().try_for_each(|m| stacks.move_multiple(m))
+}
+
+/// The crane working through the moves one step at a time.
+pub struct Crane {
+    stacks: Stacks,
+    moves: Vec<Move>,
+    done: usize,
+    /// Whether it's the `CrateMover 9001`, which moves several crates at once.
+    multiple: bool,
+    error: Option<&'static str>,
+}
+
+impl Crane {
+    /// Sets up the crane before the first of `moves`, moving several crates
+    /// at once if `multiple`.
+    #[must_use]
+    pub fn new(stacks: Stacks, moves: Vec<Move>, multiple: bool) -> Crane {
+        Crane {
+            stacks,
+            moves,
+            done: 0,
+            multiple,
+            error: None,
+        }
+    }
+}
+
+impl Visualize for Crane {
+    fn frame(&self) -> Frame {
+        let model = if self.multiple { 9001 } else { 9000 };
+        let caption = match self.done.checked_sub(1) {
+            Some(last) => format!(
+                "CrateMover {model}, {}/{}: {}",
+                self.done,
+                self.moves.len(),
+                self.moves[last]
+            ),
+            None => format!("CrateMover {model}, initial stacks"),
+        };
+        let outcome = match self.error {
+            Some(e) => format!(", next move failed: {e}"),
+            None if self.done == self.moves.len() => {
+                format!(", top crates {}", self.stacks.get_top_items())
+            }
+            None => String::new(),
+        };
+        Frame::from_text(caption + &outcome, &self.stacks.to_string())
+    }
+
+    fn step(&mut self) -> bool {
+        let Some(m) = self.moves.get(self.done).filter(|_| self.error.is_none()) else {
+            return false;
+        };
+        let moved = if self.multiple {
+            self.stacks.move_multiple(m)
+        } else {
+            self.stacks.move_single(m)
+        };
+        if let Err(e) = moved {
+            self.error = Some(e);
+            return false;
+        }
+        self.done += 1;
+        true
+    }
+}
+
+/// The solution: the top crates after the rearrangement with each crane.
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Input = (Stacks, Vec<Move>);
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_a((stacks, moves): &Self::Input) -> aoc::Result<Answer> {
+        let mut stacks = stacks.clone();
+        rearrange_single(&mut stacks, moves)?;
+        Ok(stacks.get_top_items().into())
+    }
+
+    fn part_b((stacks, moves): &Self::Input) -> aoc::Result<Answer> {
+        let mut stacks = stacks.clone();
+        rearrange_multiple(&mut stacks, moves)?;
+        Ok(stacks.get_top_items().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../sample.txt");
+
+    #[test]
+    fn invalid_move() {
+        let input = EXAMPLE.replace("move 3 from 1 to 3", "move 3 from x to 3");
+        let err = parse_input(&input).unwrap_err();
+        assert_eq!(err.position(), Some((7, 13)));
+    }
+
+    #[test]
+    fn move_outside_stacks() {
+        let input = EXAMPLE.replace("move 1 from 1 to 2", "move 1 from 1 to 4");
+        assert_eq!(parse_input(&input).unwrap_err().position(), Some((9, 1)));
+    }
+
+    #[test]
+    fn stack_zero() {
+        let err = Move::from_str("move 1 from 0 to 1").unwrap_err();
+        assert_eq!(err.position(), Some((1, 13)));
+    }
+
+    #[test]
+    fn crate_outside_stacks() {
+        let err = Stacks::from_str("[A] [B]\n 1").unwrap_err();
+        assert_eq!(err.position(), Some((1, 6)));
+    }
+
+    #[test]
+    fn display_example() {
+        let (stacks, moves) = parse_input(EXAMPLE).unwrap();
+        let moves = moves.iter().map(Move::to_string).join("\n");
+        assert_eq!(format!("{stacks}\n\n{moves}\n"), EXAMPLE);
+    }
+
+    #[test]
+    fn crane_frames() {
+        let (stacks, moves) = parse_input(EXAMPLE).unwrap();
+        let mut crane = Crane::new(stacks, moves, false);
+        assert_eq!(crane.frame().caption, "CrateMover 9000, initial stacks");
+        let mut steps = 0;
+        while crane.step() {
+            steps += 1;
+        }
+        assert_eq!(steps, 4);
+        let frame = crane.frame();
+        assert!(frame
+            .caption
+            .ends_with("4/4: move 1 from 1 to 2, top crates CMZ"));
+        assert_eq!(frame.cells.row(0)[8..], ['[', 'Z', ']']);
+    }
+
+    #[test]
+    fn impossible_moves() {
+        let (stacks, _) = parse_input(EXAMPLE).unwrap();
+        let mut moved = stacks.clone();
+        for m in ["move 3 from 1 to 2", "move 1 from 2 to 4"] {
+            let m = Move::from_str(m).unwrap();
+            assert!(moved.move_single(&m).is_err());
+            assert!(moved.move_multiple(&m).is_err());
+        }
+        assert_eq!(moved, stacks);
+        assert!(
+            rearrange_multiple(&mut moved, &[Move::from_str("move 9 from 3 to 1").unwrap()])
+                .is_err()
+        );
+    }
+
+    fn stacks() -> impl Strategy<Value = Stacks> {
+        let stack = prop::collection::vec_deque(prop::char::range('A', 'Z'), 0..8);
+        prop::collection::vec(stack, 1..10).prop_map(|stacks| Stacks { stacks })
+    }
+
+    fn moves() -> impl Strategy<Value = Move> {
+        (0..9usize, 0..9usize, 0..100usize).prop_map(|(from, to, count)| Move { from, to, count })
+    }
+
+    proptest! {
+        #[test]
+        fn stacks_round_trip(stacks in stacks()) {
+            prop_assert_eq!(stacks.to_string().parse::<Stacks>().unwrap(), stacks);
+        }
+
+        #[test]
+        fn move_round_trip(m in moves()) {
+            prop_assert_eq!(m.to_string().parse::<Move>().unwrap(), m);
+        }
+    }
+}
@@ -0,0 +1,97 @@
+#![warn(missing_docs)]
+
+//! Day 6: Tuning Trouble, start markers in a datastream.
+
+use aoc::{Answer, Solution};
+use std::collections::HashSet;
+
+/// Returns the number of bytes read once the last `window_size` bytes were all distinct.
+#[must_use]
+pub fn find_unique_byte_window(input: &str, window_size: usize) -> Option<usize> {
+    if window_size == 0 {
+        return Some(0);
+    }
+    input
+        .as_bytes()
+        .windows(window_size)
+        .position(|window| window.iter().collect::<HashSet<_>>().len() == window_size)
+        .map(|i| i + window_size)
+}
+
+/// The solution: the end of the first start-of-packet and start-of-message
+/// markers.
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Input = String;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Ok(input.to_string())
+    }
+
+    fn part_a(input: &Self::Input) -> aoc::Result<Answer> {
+        Ok(find_unique_byte_window(input, 4)
+            .ok_or("No start-of-packet marker")?
+            .into())
+    }
+
+    fn part_b(input: &Self::Input) -> aoc::Result<Answer> {
+        Ok(find_unique_byte_window(input, 14)
+            .ok_or("No start-of-message marker")?
+            .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_a() {
+        assert_eq!(
+            find_unique_byte_window("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4).unwrap(),
+            7
+        );
+        assert_eq!(
+            find_unique_byte_window("bvwbjplbgvbhsrlpgdmjqwftvncz", 4).unwrap(),
+            5
+        );
+        assert_eq!(
+            find_unique_byte_window("nppdvjthqldpwncqszvftbrmjlhg", 4).unwrap(),
+            6
+        );
+        assert_eq!(
+            find_unique_byte_window("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 4).unwrap(),
+            10
+        );
+        assert_eq!(
+            find_unique_byte_window("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4).unwrap(),
+            11
+        );
+    }
+
+    #[test]
+    fn examples_b() {
+        assert_eq!(
+            find_unique_byte_window("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14).unwrap(),
+            19
+        );
+        assert_eq!(
+            find_unique_byte_window("bvwbjplbgvbhsrlpgdmjqwftvncz", 14).unwrap(),
+            23
+        );
+        assert_eq!(
+            find_unique_byte_window("nppdvjthqldpwncqszvftbrmjlhg", 14).unwrap(),
+            23
+        );
+        assert_eq!(
+            find_unique_byte_window("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 14).unwrap(),
+            29
+        );
+        assert_eq!(
+            find_unique_byte_window("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14).unwrap(),
+            26
+        );
+    }
+}
@@ -0,0 +1,338 @@
+#![warn(clippy::pedantic)]
+#![warn(missing_docs)]
+
+//! Day 7: No Space Left On Device, directory sizes of a browsed file system.
+
+use aoc::{parse, Answer, ParseError, Solution};
+use itertools::Itertools;
+use std::cell::RefCell;
+use std::cmp;
+use std::rc::{Rc, Weak};
+
+/// A directory, whose size is the total size of everything in it.
+pub struct DirNode {
+    name: String,
+    size: usize,
+    children: Vec<Rc<RefCell<FSNode>>>,
+    // Weak, as a strong link back to the parent would keep the tree alive forever.
+    parent: Option<Weak<RefCell<FSNode>>>,
+}
+
+/// A file with its size.
+pub struct FileNode {
+    name: String,
+    size: usize,
+    parent: Option<Weak<RefCell<FSNode>>>,
+}
+
+impl DirNode {
+    fn find_child(&self, name: &str) -> Option<&Rc<RefCell<FSNode>>> {
+        self.children
+            .iter()
+            .filter(|&c| match &*c.borrow() {
+                FSNode::Dir(d) => d.name == name,
+                FSNode::File(f) => f.name == name,
+            })
+            .nth(0)
+    }
+}
+
+/// Node of the file system tree reconstructed from the terminal transcript.
+pub enum FSNode {
+    /// A directory with its children.
+    Dir(DirNode),
+    /// A file, always a leaf of the tree.
+    File(FileNode),
+}
+
+impl FSNode {
+    /// Returns the name of the node, `/` for the root directory.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            FSNode::Dir(d) => &d.name,
+            FSNode::File(f) => &f.name,
+        }
+    }
+
+    /// Returns the size of a file, or the total size of a directory.
+    #[must_use]
+    pub fn get_size(&self) -> usize {
+        match self {
+            FSNode::Dir(d) => d.size,
+            FSNode::File(f) => f.size,
+        }
+    }
+
+    fn add_size(&mut self, size: usize) -> Result<&mut FSNode, &'static str> {
+        let total = match self {
+            FSNode::Dir(d) => &mut d.size,
+            FSNode::File(f) => &mut f.size,
+        };
+        *total = total.checked_add(size).ok_or("Total size overflows")?;
+        Ok(self)
+    }
+
+    fn get_parent(&self) -> Option<Rc<RefCell<FSNode>>> {
+        match self {
+            FSNode::Dir(d) => d.parent.as_ref().and_then(Weak::upgrade),
+            FSNode::File(d) => d.parent.as_ref().and_then(Weak::upgrade),
+        }
+    }
+
+    /// Returns the nodes in a directory, which a file has none of.
+    #[must_use]
+    pub fn children(&self) -> &[Rc<RefCell<FSNode>>] {
+        match self {
+            FSNode::Dir(d) => &d.children,
+            FSNode::File(_) => &[],
+        }
+    }
+
+    fn find_child(&self, name: &str) -> Option<&Rc<RefCell<FSNode>>> {
+        match self {
+            FSNode::Dir(d) => d.find_child(name),
+            FSNode::File(_) => None,
+        }
+    }
+
+    fn add_child(&mut self, child: Rc<RefCell<FSNode>>) -> Result<&mut FSNode, &'static str> {
+        match self {
+            FSNode::Dir(d) => {
+                d.children.push(child);
+                Ok(self)
+            }
+            FSNode::File(_) => Err("Cannot add children to FileNode"),
+        }
+    }
+}
+
+/// Rebuilds the file system tree from a terminal transcript.
+///
+/// # Errors
+///
+/// Fails if the transcript doesn't start at the root, leaves it with `$ cd ..`
+/// or refers to unknown directories.
+pub fn parse_fs(input: &str) -> Result<Rc<RefCell<FSNode>>, ParseError> {
+    let mut lines = input.lines();
+    match lines.next() {
+        Some("$ cd /") => (),
+        Some(line) => return Err(ParseError::line("expected `$ cd /`", line)),
+        None => return Err(ParseError::input("empty terminal transcript")),
+    }
+
+    let root = Rc::new(RefCell::new(FSNode::Dir(DirNode {
+        name: "/".to_string(),
+        size: 0,
+        children: vec![],
+        parent: None,
+    })));
+    let mut current = Rc::clone(&root);
+
+    for (i, line) in lines.enumerate() {
+        let err =
+            |message: &str, span: &str| ParseError::new(message, line, span).shift_lines(i + 1);
+        let name = line.split(' ').next_back().unwrap_or_default();
+
+        if line == "$ ls" {
+            // just assume the input is always valid...
+        } else if line == "$ cd .." {
+            let parent = current
+                .borrow()
+                .get_parent()
+                .ok_or_else(|| err("cannot leave the root directory", line))?;
+            parent
+                .borrow_mut()
+                .add_size(current.borrow().get_size())
+                .map_err(|e| err(e, line))?;
+            current = parent;
+        } else if line.starts_with("$ cd") {
+            let child = Rc::clone(
+                current
+                    .borrow()
+                    .find_child(name)
+                    .ok_or_else(|| err("cd to unknown child", name))?,
+            );
+            current = child;
+        } else if line.starts_with("dir") {
+            current
+                .borrow_mut()
+                .add_child(Rc::new(RefCell::new(FSNode::Dir(DirNode {
+                    name: name.to_string(),
+                    size: 0,
+                    children: vec![],
+                    parent: Some(Rc::downgrade(&current)),
+                }))))
+                .map_err(|e| err(e, line))?;
+        } else {
+            let (size, name) = line
+                .split(' ')
+                .collect_tuple::<(_, _)>()
+                .ok_or_else(|| err("expected `<size> <name>`", line))?;
+            let size = parse::token::<usize>(line, size).map_err(|e| e.shift_lines(i + 1))?;
+            current
+                .borrow_mut()
+                .add_child(Rc::new(RefCell::new(FSNode::File(FileNode {
+                    name: name.to_string(),
+                    size,
+                    parent: Some(Rc::downgrade(&current)),
+                }))))
+                .and_then(|node| node.add_size(size))
+                .map_err(|e| err(e, line))?;
+        }
+    }
+
+    loop {
+        let parent = current.borrow().get_parent();
+
+        if let Some(p) = parent {
+            p.borrow_mut()
+                .add_size(current.borrow().get_size())
+                .map_err(ParseError::input)?;
+            current = p;
+        } else {
+            break;
+        }
+    }
+
+    Ok(root)
+}
+
+/// Sums the sizes of all directories smaller than 100000.
+#[must_use]
+pub fn calc_small_directories_sum(fs: &Rc<RefCell<FSNode>>) -> usize {
+    let mut queue: Vec<Rc<RefCell<FSNode>>> = vec![];
+    let mut size = 0;
+
+    queue.push(Rc::clone(fs));
+    while let Some(node) = queue.pop() {
+        match &*node.borrow() {
+            FSNode::Dir(d) => {
+                if d.size < 100_000 {
+                    size += d.size;
+                }
+                d.children.iter().for_each(|c| queue.push(Rc::clone(c)));
+            }
+            FSNode::File(_) => (),
+        };
+    }
+
+    size
+}
+
+/// Returns the size of the smallest directory that frees up enough space for the update.
+///
+/// # Errors
+///
+/// Fails if no directory is large enough.
+pub fn find_small_directory_above(fs: &Rc<RefCell<FSNode>>) -> Result<usize, &'static str> {
+    let mut queue: Vec<Rc<RefCell<FSNode>>> = vec![];
+    let mut result_size: Option<usize> = None;
+    let root_size = fs.borrow().get_size();
+    let min_size = root_size
+        .saturating_add(30_000_000)
+        .saturating_sub(70_000_000);
+
+    queue.push(Rc::clone(fs));
+    while let Some(node) = queue.pop() {
+        match &*node.borrow() {
+            FSNode::Dir(d) => {
+                if d.size >= min_size {
+                    result_size = Some(match result_size {
+                        Some(s) => cmp::min(s, d.size),
+                        None => d.size,
+                    });
+                }
+                d.children.iter().for_each(|c| queue.push(Rc::clone(c)));
+            }
+            FSNode::File(_) => (),
+        };
+    }
+
+    result_size.ok_or("No directory found that can be deleted to reach enough free space")
+}
+
+/// The solution: the total size of the small directories, then the size of
+/// the directory to delete.
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    type Input = Rc<RefCell<FSNode>>;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Ok(parse_fs(input)?)
+    }
+
+    fn part_a(fs: &Self::Input) -> aoc::Result<Answer> {
+        Ok(calc_small_directories_sum(fs).into())
+    }
+
+    fn part_b(fs: &Self::Input) -> aoc::Result<Answer> {
+        Ok(find_small_directory_above(fs)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_DATA: &str = "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k";
+
+    #[test]
+    fn task_a() {
+        let fs = parse_fs(EXAMPLE_DATA).unwrap();
+        assert_eq!(calc_small_directories_sum(&fs), 95437);
+
+        let root = fs.borrow();
+        let names = root
+            .children()
+            .iter()
+            .map(|c| c.borrow().name().to_string());
+        assert_eq!(names.collect::<Vec<_>>(), ["a", "b.txt", "c.dat", "d"]);
+        assert_eq!(root.get_size(), 48_381_165);
+    }
+
+    #[test]
+    fn invalid_lines() {
+        let input = EXAMPLE_DATA.replace("29116 f", "29x16 f");
+        assert_eq!(parse_fs(&input).err().unwrap().position(), Some((10, 1)));
+
+        let input = EXAMPLE_DATA.replace("$ cd e", "$ cd x");
+        assert_eq!(parse_fs(&input).err().unwrap().position(), Some((13, 6)));
+
+        let input = EXAMPLE_DATA.replace("$ cd d", "$ cd ..");
+        assert_eq!(parse_fs(&input).err().unwrap().position(), Some((18, 1)));
+
+        assert!(parse_fs("$ ls").is_err());
+        assert!(parse_fs("").is_err());
+    }
+
+    #[test]
+    fn task_b() {
+        let fs = parse_fs(EXAMPLE_DATA).unwrap();
+        assert_eq!(find_small_directory_above(&fs).unwrap(), 24_933_642);
+    }
+}
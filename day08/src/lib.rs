@@ -0,0 +1,211 @@
+#![warn(missing_docs)]
+
+//! Day 8: Treetop Tree House, visibility and scenic scores of trees.
+
+use aoc::export::Rgb;
+use aoc::geom::{Direction, Point};
+use aoc::grid::Grid;
+use aoc::{Answer, ParseError, Solution};
+use std::fmt;
+
+/// Height of a single tree, from 0 to 9.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Height(u8);
+
+impl Height {
+    /// Returns the height `h`, or `None` if it is above 9.
+    #[must_use]
+    pub fn new(h: u8) -> Option<Height> {
+        (h <= 9).then_some(Height(h))
+    }
+
+    /// Returns the height as a number.
+    #[must_use]
+    pub fn get(self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<char> for Height {
+    type Error = &'static str;
+
+    fn try_from(c: char) -> Result<Height, Self::Error> {
+        c.to_digit(10)
+            .map(|h| Height(h as u8))
+            .ok_or("tree height must be a digit")
+    }
+}
+
+impl fmt::Display for Height {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Parses the map of tree heights, one digit per tree.
+///
+/// # Errors
+///
+/// Fails on empty input, non-digits and rows of different lengths.
+pub fn parse_forest(input: &str) -> Result<Grid<Height>, ParseError> {
+    Grid::parse(input, Height::try_from)
+}
+
+/// Checks whether the tree at `pos` can be seen from outside the grid.
+///
+/// # Panics
+///
+/// Panics if `pos` lies outside the grid.
+#[must_use]
+pub fn is_visible(grid: &Grid<Height>, pos: Point) -> bool {
+    let height = &grid[pos];
+    Direction::ALL
+        .into_iter()
+        .any(|dir| grid.ray(pos, dir).all(|(_, other)| other < height))
+}
+
+/// Counts the trees visible from outside the grid.
+#[must_use]
+pub fn count_visible_trees(grid: &Grid<Height>) -> usize {
+    grid.points().filter(|&pos| is_visible(grid, pos)).count()
+}
+
+/// Draws the forest in shades of green, lighter for taller trees, with the
+/// trees that can't be seen from outside the grid darkened.
+#[must_use]
+pub fn forest_image(grid: &Grid<Height>) -> Grid<Rgb> {
+    const SHORT: Rgb = Rgb::new(25, 70, 25);
+    const TALL: Rgb = Rgb::new(160, 235, 120);
+    Grid::from_fn(grid.width(), grid.height(), |pos| {
+        let colour = SHORT.mix(TALL, f64::from(grid[pos].0) / 9.0);
+        if is_visible(grid, pos) {
+            colour
+        } else {
+            colour.mix(Rgb::BLACK, 0.6)
+        }
+    })
+}
+
+/// Returns the scenic score of the tree at `pos`, the product of how many
+/// trees it sees in each direction.
+///
+/// # Panics
+///
+/// Panics if `pos` lies outside the grid.
+#[must_use]
+pub fn scenic_score(grid: &Grid<Height>, pos: Point) -> usize {
+    let height = &grid[pos];
+    Direction::ALL
+        .into_iter()
+        .map(|dir| {
+            let mut seen = 0;
+            for (_, other) in grid.ray(pos, dir) {
+                seen += 1;
+                if other >= height {
+                    break; // the view is blocked by this tree
+                }
+            }
+            seen
+        })
+        .product()
+}
+
+/// Returns the highest scenic score of any tree.
+#[must_use]
+pub fn calc_max_tree_score(grid: &Grid<Height>) -> usize {
+    grid.points()
+        .map(|pos| scenic_score(grid, pos))
+        .max()
+        .unwrap_or(0)
+}
+
+/// The solution: the number of visible trees, then the highest scenic score.
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    type Input = Grid<Height>;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Ok(parse_forest(input)?)
+    }
+
+    fn part_a(grid: &Self::Input) -> aoc::Result<Answer> {
+        Ok(count_visible_trees(grid).into())
+    }
+
+    fn part_b(grid: &Self::Input) -> aoc::Result<Answer> {
+        Ok(calc_max_tree_score(grid).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE_DATA: &str = "30373\n25512\n65332\n33549\n35390";
+
+    #[test]
+    fn invalid_grid() {
+        let err = parse_forest("303\n2x5\n653").err().unwrap();
+        assert_eq!(err.position(), Some((2, 2)));
+
+        let err = parse_forest("303\n25\n653").err().unwrap();
+        assert_eq!(err.position(), Some((2, 1)));
+
+        assert!(parse_forest("").is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn grid_round_trip(
+            (width, height, heights) in (1..20usize, 1..20usize).prop_flat_map(|(w, h)| {
+                (Just(w), Just(h), prop::collection::vec(0..=9u8, w * h))
+            })
+        ) {
+            let grid = Grid::new(width, height, heights.into_iter().map(Height).collect());
+            prop_assert_eq!(parse_forest(&grid.to_string()).unwrap(), grid);
+        }
+    }
+
+    #[test]
+    fn display_example() {
+        assert_eq!(
+            parse_forest(EXAMPLE_DATA).unwrap().to_string(),
+            EXAMPLE_DATA
+        );
+    }
+
+    #[test]
+    fn test_a() {
+        assert_eq!(
+            count_visible_trees(&parse_forest(EXAMPLE_DATA).unwrap()),
+            21
+        );
+    }
+
+    #[test]
+    fn non_square_grid() {
+        let grid = parse_forest("123\n456").unwrap();
+        assert_eq!(count_visible_trees(&grid), 6);
+        assert_eq!(calc_max_tree_score(&grid), 0);
+    }
+
+    #[test]
+    fn image_darkens_hidden_trees() {
+        let grid = parse_forest(EXAMPLE_DATA).unwrap();
+        let image = forest_image(&grid);
+        assert_eq!((image.width(), image.height()), (5, 5));
+        // Of the two inner trees of height 3 in the middle row only the right
+        // one is visible.
+        let (hidden, visible) = (image[Point::new(2, 2)], image[Point::new(3, 2)]);
+        assert_eq!(grid[Point::new(2, 2)], grid[Point::new(3, 2)]);
+        assert!(hidden.g < visible.g);
+    }
+
+    #[test]
+    fn test_b() {
+        assert_eq!(calc_max_tree_score(&parse_forest(EXAMPLE_DATA).unwrap()), 8);
+    }
+}
@@ -0,0 +1,396 @@
+#![warn(clippy::pedantic)]
+#![warn(missing_docs)]
+
+//! Day 9: Rope Bridge, the positions visited by the tail of a rope.
+
+use aoc::export::Rgb;
+use aoc::geom::{Direction, Point};
+use aoc::grid::Grid;
+use aoc::stream::for_each_line;
+use aoc::visual::{Frame, Visualize};
+use aoc::{parse, Answer, ParseError, Solution};
+use itertools::Itertools;
+use std::collections::HashSet;
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// A motion of the head of the rope.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Move {
+    /// Number of single steps.
+    pub count: usize,
+    /// Direction of every step.
+    pub dir: Direction,
+}
+
+/// A rope with its knots, remembering the positions its tail has visited.
+pub struct Rope {
+    heads: Vec<Point>,
+    visited: HashSet<Point>,
+}
+
+impl Rope {
+    /// Creates a rope of `n_heads` knots, at least one, all at the origin.
+    #[must_use]
+    pub fn new(n_heads: usize) -> Rope {
+        Rope {
+            heads: std::iter::repeat_n(Point::ORIGIN, n_heads.max(1)).collect(),
+            visited: HashSet::new(),
+        }
+    }
+
+    /// Returns the knots from the head to the tail.
+    #[must_use]
+    pub fn knots(&self) -> &[Point] {
+        &self.heads
+    }
+
+    /// Returns the positions the tail has been at after each step.
+    #[must_use]
+    pub fn visited(&self) -> &HashSet<Point> {
+        &self.visited
+    }
+
+    fn update_tail(head: Point, tail: &mut Point) {
+        if head.chebyshev(*tail) > 1 {
+            *tail += (head - *tail).signum();
+        }
+    }
+
+    /// Moves the head one step towards `d`, lets the other knots follow and
+    /// records where the tail ends up.
+    pub fn step(&mut self, d: Direction) {
+        self.heads[0] += d;
+        let mut ahead = self.heads[0];
+        for knot in &mut self.heads[1..] {
+            Rope::update_tail(ahead, knot);
+            ahead = *knot;
+        }
+        self.visited.insert(ahead);
+    }
+
+    /// Moves the head through all steps of `m`.
+    pub fn apply(&mut self, m: &Move) {
+        for _ in 0..m.count {
+            self.step(m.dir);
+        }
+    }
+}
+
+fn parse_direction(input: &str) -> Result<Direction, ParseError> {
+    match input {
+        "U" => Ok(Direction::Up),
+        "D" => Ok(Direction::Down),
+        "L" => Ok(Direction::Left),
+        "R" => Ok(Direction::Right),
+        d => Err(ParseError::line(format!("invalid direction `{d}`"), d)),
+    }
+}
+
+impl FromStr for Move {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Move, Self::Err> {
+        match input.split(' ').collect_tuple::<(_, _)>() {
+            Some((dir, count)) => Ok(Move {
+                count: parse::token(input, count)?,
+                dir: parse_direction(dir).map_err(|e| e.within(input, dir))?,
+            }),
+            None => Err(ParseError::line("expected `<direction> <count>`", input)),
+        }
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dir = match self.dir {
+            Direction::Up => "U",
+            Direction::Down => "D",
+            Direction::Left => "L",
+            Direction::Right => "R",
+        };
+        write!(f, "{dir} {}", self.count)
+    }
+}
+
+/// Parses one move per line.
+///
+/// # Errors
+///
+/// Fails on the first line that isn't a valid move.
+pub fn parse_moves(input: &str) -> Result<Vec<Move>, ParseError> {
+    parse::lines(input, Move::from_str)
+}
+
+/// Counts the positions visited by the tail of a rope with `n_heads` knots,
+/// at least one.
+#[must_use]
+pub fn count_unqiue_tail_positions(moves: &[Move], n_heads: usize) -> usize {
+    let mut rope = Rope::new(n_heads);
+    for m in moves {
+        rope.apply(m);
+    }
+    rope.visited.len()
+}
+
+/// The rope following its moves one step of the head at a time.
+pub struct RopeMotion {
+    rope: Rope,
+    moves: Vec<Move>,
+    /// Number of moves started so far.
+    started: usize,
+    /// Steps left of the current move.
+    steps_left: usize,
+}
+
+impl RopeMotion {
+    /// Sets up a rope of `n_heads` knots, at least one, before the first of
+    /// `moves`.
+    #[must_use]
+    pub fn new(moves: Vec<Move>, n_heads: usize) -> RopeMotion {
+        RopeMotion {
+            rope: Rope::new(n_heads),
+            moves,
+            started: 0,
+            steps_left: 0,
+        }
+    }
+
+    /// Returns the label of knot `i`, as in the puzzle's drawings.
+    fn label(&self, i: usize) -> char {
+        match i {
+            0 => 'H',
+            1 if self.rope.heads.len() == 2 => 'T',
+            i => u32::try_from(i)
+                .ok()
+                .and_then(|i| char::from_digit(i, 36))
+                .unwrap_or('*'),
+        }
+    }
+}
+
+impl Visualize for RopeMotion {
+    /// Draws the area the rope has covered: the knots, the start `s` and the
+    /// positions the tail has visited `#`.
+    fn frame(&self) -> Frame {
+        let points = || {
+            self.rope
+                .heads
+                .iter()
+                .chain(&self.rope.visited)
+                .chain([&Point::ORIGIN])
+        };
+        let min = Point::new(
+            points().map(|p| p.x).min().unwrap_or(0),
+            points().map(|p| p.y).min().unwrap_or(0),
+        );
+        let max = Point::new(
+            points().map(|p| p.x).max().unwrap_or(0),
+            points().map(|p| p.y).max().unwrap_or(0),
+        );
+        let size = max - min + Point::new(1, 1);
+        let cells = Grid::from_fn(size.x.unsigned_abs(), size.y.unsigned_abs(), |p| {
+            let p = p + min;
+            match self.rope.heads.iter().position(|&k| k == p) {
+                Some(i) => self.label(i),
+                None if p == Point::ORIGIN => 's',
+                None if self.rope.visited.contains(&p) => '#',
+                None => '.',
+            }
+        });
+
+        let caption = match self.started.checked_sub(1) {
+            Some(current) => format!(
+                "{}/{}: {}, tail visited {} positions",
+                self.started,
+                self.moves.len(),
+                self.moves[current],
+                self.rope.visited.len()
+            ),
+            None => "start".to_string(),
+        };
+        Frame::new(caption, cells)
+            .with_focus(self.rope.heads[0] - min)
+            .with_origin(min)
+    }
+
+    fn step(&mut self) -> bool {
+        while self.steps_left == 0 {
+            let Some(m) = self.moves.get(self.started) else {
+                return false;
+            };
+            self.steps_left = m.count;
+            self.started += 1;
+        }
+        self.steps_left -= 1;
+        let dir = self.moves[self.started - 1].dir;
+        self.rope.step(dir);
+        true
+    }
+}
+
+/// Colours a cell of a [`RopeMotion`] frame for image export: the head red,
+/// the other knots orange, the start blue and the visited positions grey on
+/// a dark background.
+#[must_use]
+pub fn frame_colour(cell: char) -> Rgb {
+    match cell {
+        'H' => Rgb::new(230, 60, 50),
+        's' => Rgb::new(70, 130, 230),
+        '#' => Rgb::new(170, 170, 160),
+        'T' | '0'..='9' | 'a'..='z' | '*' => Rgb::new(245, 165, 40),
+        _ => Rgb::new(25, 25, 35),
+    }
+}
+
+/// Numbers of positions visited by the tails of the ropes of both parts.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TailVisits {
+    /// Positions visited by the tail of the rope of part A.
+    pub two_knots: usize,
+    /// Positions visited by the tail of the rope of part B.
+    pub ten_knots: usize,
+}
+
+/// Moves both ropes one line at a time, so memory grows with the area the
+/// tails cover rather than the number of moves.
+///
+/// # Errors
+///
+/// Fails on the first line that isn't a valid move.
+pub fn count_tail_visits(input: impl BufRead) -> Result<TailVisits, ParseError> {
+    let (mut short, mut long) = (Rope::new(2), Rope::new(10));
+    for_each_line(input, |line| {
+        let m = Move::from_str(line)?;
+        short.apply(&m);
+        long.apply(&m);
+        Ok(())
+    })?;
+    Ok(TailVisits {
+        two_knots: short.visited.len(),
+        ten_knots: long.visited.len(),
+    })
+}
+
+/// The solution: the positions visited by the tail of a rope of 2, then 10
+/// knots.
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    const SOLVED_WHILE_PARSING: bool = true;
+    type Input = TailVisits;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Self::parse_reader(input.as_bytes())
+    }
+
+    fn parse_reader(input: impl BufRead) -> aoc::Result<Self::Input> {
+        Ok(count_tail_visits(input)?)
+    }
+
+    fn part_a(visits: &Self::Input) -> aoc::Result<Answer> {
+        Ok(visits.two_knots.into())
+    }
+
+    fn part_b(visits: &Self::Input) -> aoc::Result<Answer> {
+        Ok(visits.ten_knots.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE_A: &str = "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2";
+    const EXAMPLE_B: &str = "R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20";
+
+    #[test]
+    fn invalid_moves() {
+        let err = parse_moves("R 4\nU 4\nX 3").err().unwrap();
+        assert_eq!(err.position(), Some((3, 1)));
+
+        let err = parse_moves("R 4\nU -4").err().unwrap();
+        assert_eq!(err.position(), Some((2, 3)));
+    }
+
+    fn moves() -> impl Strategy<Value = Move> {
+        let dir = prop_oneof![
+            Just(Direction::Up),
+            Just(Direction::Down),
+            Just(Direction::Left),
+            Just(Direction::Right),
+        ];
+        (dir, any::<usize>()).prop_map(|(dir, count)| Move { count, dir })
+    }
+
+    proptest! {
+        #[test]
+        fn move_round_trip(m in moves()) {
+            prop_assert_eq!(m.to_string().parse::<Move>().unwrap(), m);
+        }
+    }
+
+    #[test]
+    fn display_example() {
+        let moves = parse_moves(EXAMPLE_B).unwrap();
+        assert_eq!(moves.iter().map(Move::to_string).join("\n"), EXAMPLE_B);
+    }
+
+    #[test]
+    fn example_a() {
+        assert_eq!(
+            count_unqiue_tail_positions(&parse_moves(EXAMPLE_A).unwrap(), 2),
+            13
+        );
+        assert_eq!(
+            count_unqiue_tail_positions(&parse_moves(EXAMPLE_A).unwrap(), 10),
+            1
+        );
+    }
+
+    #[test]
+    fn example_b() {
+        assert_eq!(
+            count_unqiue_tail_positions(&parse_moves(EXAMPLE_B).unwrap(), 10),
+            36
+        );
+    }
+
+    #[test]
+    fn rope_frames() {
+        let mut motion = RopeMotion::new(parse_moves(EXAMPLE_A).unwrap(), 2);
+        assert_eq!(motion.frame().cells.to_string(), "H");
+        for _ in 0..4 {
+            assert!(motion.step());
+        }
+        let frame = motion.frame();
+        assert_eq!(frame.caption, "1/8: R 4, tail visited 4 positions");
+        assert_eq!(frame.cells.to_string(), "s##TH");
+        assert_eq!(frame.focus, Some(Point::new(4, 0)));
+
+        while motion.step() {}
+        assert!(motion
+            .frame()
+            .caption
+            .ends_with("tail visited 13 positions"));
+    }
+
+    #[test]
+    fn frame_colours() {
+        let background = frame_colour('.');
+        assert_eq!(frame_colour(' '), background);
+        let colours = ['H', 'T', '1', 's', '#'].map(frame_colour);
+        assert!(colours.iter().all(|&c| c != background));
+    }
+
+    #[test]
+    fn streamed() {
+        let visits = count_tail_visits(EXAMPLE_B.as_bytes()).unwrap();
+        assert_eq!(visits.ten_knots, 36);
+        let err = count_tail_visits("R 4\n\nL 3".as_bytes()).unwrap_err();
+        assert_eq!(err.position().map(|(line, _)| line), Some(2));
+    }
+}
@@ -1,148 +1,6 @@
-#![warn(clippy::pedantic)]
+use day09::Day09;
+use std::process::ExitCode;
 
-use itertools::Itertools;
-use std::collections::HashSet;
-use std::error::Error;
-use std::fs;
-use std::iter;
-use std::str::FromStr;
-
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    Left,
-    Right,
-    Up,
-    Down,
-}
-
-struct Move {
-    count: usize,
-    dir: Direction,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Pos {
-    x: isize,
-    y: isize,
-}
-
-struct Rope {
-    heads: Vec<Pos>,
-}
-
-impl Rope {
-    fn new(n_heads: usize) -> Rope {
-        Rope {
-            heads: iter::repeat(Pos { x: 0, y: 0 }).take(n_heads).collect(),
-        }
-    }
-
-    fn update_tail(head: Pos, tail: &mut Pos) {
-        let dx = tail.x - head.x;
-        let dy = tail.y - head.y;
-        if dx.abs() < 2 && dy.abs() < 2 {
-            // head and tail touch
-        } else {
-            tail.x -= dx.signum();
-            tail.y -= dy.signum();
-        }
-    }
-
-    fn move_head(&mut self, d: &Direction) -> &Pos {
-        match d {
-            Direction::Up => self.heads[0].y += 1,
-            Direction::Down => self.heads[0].y -= 1,
-            Direction::Right => self.heads[0].x += 1,
-            Direction::Left => self.heads[0].x -= 1,
-        }
-
-        for i in 1..self.heads.len() {
-            Rope::update_tail(self.heads[i - 1], &mut self.heads[i]);
-        }
-
-        self.heads.last().unwrap()
-    }
-}
-
-impl FromStr for Direction {
-    type Err = Box<dyn Error>;
-
-    fn from_str(input: &str) -> Result<Direction, Self::Err> {
-        match input {
-            "U" => Ok(Direction::Up),
-            "D" => Ok(Direction::Down),
-            "L" => Ok(Direction::Left),
-            "R" => Ok(Direction::Right),
-            d => Err(format!("Invalid direction: {}", d))?,
-        }
-    }
-}
-
-impl FromStr for Move {
-    type Err = Box<dyn Error>;
-
-    fn from_str(input: &str) -> Result<Move, Self::Err> {
-        match input.split(" ").collect_tuple::<(_, _)>() {
-            Some((dir, count)) => Ok(Move {
-                count: count.parse::<usize>()?,
-                dir: Direction::from_str(dir)?,
-            }),
-            None => Err("Invalid input line: couldn't split")?,
-        }
-    }
-}
-
-fn parse_moves(input: &str) -> Result<Vec<Move>, Box<dyn Error>> {
-    input
-        .lines()
-        .map(|line| Move::from_str(line))
-        .collect::<Result<Vec<_>, _>>()
-}
-
-fn count_unqiue_tail_positions(moves: &Vec<Move>, n_heads: usize) -> usize {
-    let mut rope = Rope::new(n_heads);
-
-    moves
-        .iter()
-        .map(|m| iter::repeat(m.dir).take(m.count))
-        .flatten()
-        .map(|d| *rope.move_head(&d))
-        .collect::<HashSet<_>>()
-        .len()
-}
-
-fn main() -> Result<(), Box<dyn Error>> {
-    let moves = parse_moves(&fs::read_to_string("input.txt")?)?;
-
-    println!("A: {}", count_unqiue_tail_positions(&moves, 2));
-    println!("B: {}", count_unqiue_tail_positions(&moves, 10));
-    Ok(())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_A: &'static str = "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2";
-    const EXAMPLE_B: &'static str = "R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20";
-
-    #[test]
-    fn example_a() {
-        assert_eq!(
-            count_unqiue_tail_positions(&parse_moves(EXAMPLE_A).unwrap(), 2),
-            13
-        );
-        assert_eq!(
-            count_unqiue_tail_positions(&parse_moves(EXAMPLE_A).unwrap(), 10),
-            1
-        );
-    }
-
-    #[test]
-    fn example_b() {
-        assert_eq!(
-            count_unqiue_tail_positions(&parse_moves(EXAMPLE_B).unwrap(), 10),
-            36
-        );
-    }
+fn main() -> ExitCode {
+    aoc::main::<Day09>(env!("CARGO_MANIFEST_DIR"))
 }
@@ -0,0 +1,472 @@
+#![warn(clippy::pedantic)]
+#![warn(missing_docs)]
+
+//! Day 10: Cathode-Ray Tube, a CPU driving a CRT screen.
+
+use aoc::export::Rgb;
+use aoc::stream::for_each_line;
+use aoc::visual::{Frame, Visualize};
+use aoc::{parse, Answer, ParseError, Screen, Solution};
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// Register state of the CPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CPUState {
+    /// The X register, the middle of the sprite.
+    pub x: isize,
+}
+
+impl Default for CPUState {
+    /// The state the CPU starts in, with X = 1.
+    fn default() -> CPUState {
+        CPUState { x: 1 }
+    }
+}
+
+/// A single instruction understood by the CPU, displayed in its assembly
+/// syntax.
+pub trait CPUInstruction: fmt::Display + fmt::Debug {
+    /// Executes the instruction and returns the number of cycles it took.
+    fn run(&self, state: &mut CPUState) -> usize;
+
+    /// Parses the instruction's operands from `tokens`, the remaining
+    /// tokens of `line`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if an operand is missing or malformed.
+    fn parse<'a>(
+        line: &'a str,
+        tokens: &mut impl Iterator<Item = &'a str>,
+    ) -> Result<Box<dyn CPUInstruction>, ParseError>
+    where
+        Self: Sized;
+}
+
+/// `noop`, taking one cycle without any effect.
+#[derive(Debug)]
+pub struct Nop {}
+
+/// `addx n`, adding `n` to the X register after two cycles.
+#[derive(Debug)]
+pub struct Addx {
+    /// The value added.
+    pub n: isize,
+}
+
+impl CPUInstruction for Nop {
+    fn run(&self, _state: &mut CPUState) -> usize {
+        1
+    }
+
+    fn parse<'a>(
+        _: &'a str,
+        _: &mut impl Iterator<Item = &'a str>,
+    ) -> Result<Box<dyn CPUInstruction>, ParseError> {
+        Ok(Box::new(Nop {}))
+    }
+}
+
+impl CPUInstruction for Addx {
+    fn run(&self, state: &mut CPUState) -> usize {
+        state.x = state.x.saturating_add(self.n);
+        2
+    }
+
+    fn parse<'a>(
+        line: &'a str,
+        tokens: &mut impl Iterator<Item = &'a str>,
+    ) -> Result<Box<dyn CPUInstruction>, ParseError> {
+        let n = tokens
+            .next()
+            .ok_or_else(|| ParseError::new("addx without imm value", line, &line[line.len()..]))?;
+        Ok(Box::new(Addx {
+            n: parse::token(line, n)?,
+        }))
+    }
+}
+
+impl fmt::Display for Nop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("noop")
+    }
+}
+
+impl fmt::Display for Addx {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "addx {}", self.n)
+    }
+}
+
+impl FromStr for Box<dyn CPUInstruction> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Box<dyn CPUInstruction>, ParseError> {
+        let mut tokens = s.split(' ');
+        let instr = tokens.next().unwrap_or_default();
+
+        match instr {
+            "noop" => Nop::parse(s, &mut tokens),
+            "addx" => Addx::parse(s, &mut tokens),
+            _ => Err(ParseError::new(
+                format!("unknown instruction `{instr}`"),
+                s,
+                instr,
+            )),
+        }
+    }
+}
+
+/// Parses one instruction per line.
+///
+/// # Errors
+///
+/// Fails on the first line that isn't a valid instruction.
+pub fn parse_instructions(s: &str) -> Result<Vec<Box<dyn CPUInstruction>>, ParseError> {
+    parse::lines(s, str::parse::<Box<dyn CPUInstruction>>)
+}
+
+/// What the CRT shows while the CPU runs: the sum of the signal strengths
+/// and the pixels drawn so far.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Crt {
+    cycles: usize,
+    signal_strength: isize,
+    pixels: Vec<bool>,
+}
+
+impl Crt {
+    /// Pixels per row of the screen.
+    pub const WIDTH: usize = 40;
+    /// Rows of the screen.
+    pub const HEIGHT: usize = 6;
+
+    /// Records one cycle during which the X register holds `x`.
+    ///
+    /// Signal strengths are summed during the 20th, 60th, 100th, ... cycles.
+    /// Only the first 240 cycles are drawn, filling the screen.
+    fn tick(&mut self, x: isize) {
+        let pc = self.cycles;
+        self.cycles += 1;
+        if pc % Crt::WIDTH == 19 {
+            let strength = (pc + 1).cast_signed().saturating_mul(x);
+            self.signal_strength = self.signal_strength.saturating_add(strength);
+        }
+        if self.pixels.len() < Crt::WIDTH * Crt::HEIGHT {
+            let column = (pc % Crt::WIDTH).cast_signed();
+            let lit = column.checked_sub(x).is_some_and(|d| (-1..=1).contains(&d));
+            self.pixels.push(lit);
+        }
+    }
+
+    /// Runs `instr`, recording every cycle it takes.
+    pub fn run(&mut self, cpu: &mut CPUState, instr: &dyn CPUInstruction) {
+        let x = cpu.x;
+        for _ in 0..instr.run(cpu) {
+            self.tick(x);
+        }
+    }
+
+    /// Returns the sum of the signal strengths recorded so far.
+    #[must_use]
+    pub fn signal_strength(&self) -> isize {
+        self.signal_strength
+    }
+
+    /// Returns the pixels drawn so far.
+    #[must_use]
+    pub fn screen(&self) -> Screen {
+        Screen::new(Crt::WIDTH, self.pixels.clone())
+    }
+}
+
+/// Runs the program from the initial CPU state, returning what the CRT shows
+/// at the end.
+#[must_use]
+pub fn run_cpu(instrs: &[Box<dyn CPUInstruction>]) -> Crt {
+    let mut cpu = CPUState::default();
+    let mut crt = Crt::default();
+    for instr in instrs {
+        crt.run(&mut cpu, instr.as_ref());
+    }
+    crt
+}
+
+/// Sums the signal strengths during the 20th, 60th, 100th, ... cycles.
+#[must_use]
+pub fn calc_sum(instrs: &[Box<dyn CPUInstruction>]) -> isize {
+    run_cpu(instrs).signal_strength
+}
+
+/// Returns the pixels drawn on the CRT, row by row.
+#[must_use]
+pub fn draw_screen(instrs: &[Box<dyn CPUInstruction>]) -> Vec<char> {
+    run_cpu(instrs)
+        .pixels
+        .iter()
+        .map(|&lit| if lit { '#' } else { '.' })
+        .collect()
+}
+
+/// Runs the program one instruction per line as it is read.
+///
+/// # Errors
+///
+/// Fails on the first line that isn't a valid instruction.
+pub fn run_program(input: impl BufRead) -> Result<Crt, ParseError> {
+    let mut cpu = CPUState::default();
+    let mut crt = Crt::default();
+    for_each_line(input, |line| {
+        let instr = line.parse::<Box<dyn CPUInstruction>>()?;
+        crt.run(&mut cpu, instr.as_ref());
+        Ok(())
+    })?;
+    Ok(crt)
+}
+
+/// The handheld device running a program one cycle at a time, showing the
+/// CRT and the sprite position.
+pub struct Device {
+    instrs: Vec<Box<dyn CPUInstruction>>,
+    cpu: CPUState,
+    crt: Crt,
+    /// Number of instructions started so far.
+    started: usize,
+    /// Cycles left of the current instruction.
+    cycles_left: usize,
+    /// Value of the X register while the current instruction runs.
+    x: isize,
+}
+
+impl Device {
+    /// Sets up the device before the first cycle of the program `instrs`.
+    #[must_use]
+    pub fn new(instrs: Vec<Box<dyn CPUInstruction>>) -> Device {
+        Device {
+            instrs,
+            cpu: CPUState::default(),
+            crt: Crt::default(),
+            started: 0,
+            cycles_left: 0,
+            x: 1,
+        }
+    }
+}
+
+impl Visualize for Device {
+    /// Draws the CRT, with pixels that haven't been drawn yet left blank,
+    /// and the sprite below it.
+    fn frame(&self) -> Frame {
+        let screen = (0..Crt::HEIGHT).map(|y| {
+            (0..Crt::WIDTH)
+                .map(|x| match self.crt.pixels.get(y * Crt::WIDTH + x) {
+                    Some(true) => '#',
+                    Some(false) => '.',
+                    None => ' ',
+                })
+                .collect::<String>()
+        });
+        let sprite = (0..Crt::WIDTH)
+            .map(|x| {
+                let on_sprite = x.cast_signed().checked_sub(self.x);
+                if on_sprite.is_some_and(|d| (-1..=1).contains(&d)) {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect::<String>();
+        let text = screen
+            .chain([String::new(), sprite])
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let running = match self.started.checked_sub(1) {
+            Some(current) => format!(", running `{}`", self.instrs[current]),
+            None => String::new(),
+        };
+        let caption = format!(
+            "cycle {}: X = {}, signal strength {}{running}",
+            self.crt.cycles, self.x, self.crt.signal_strength
+        );
+        Frame::from_text(caption, &text)
+    }
+
+    fn step(&mut self) -> bool {
+        while self.cycles_left == 0 {
+            let Some(instr) = self.instrs.get(self.started) else {
+                return false;
+            };
+            self.x = self.cpu.x;
+            self.cycles_left = instr.run(&mut self.cpu);
+            self.started += 1;
+        }
+        self.cycles_left -= 1;
+        self.crt.tick(self.x);
+        true
+    }
+}
+
+/// Colours a cell of a [`Device`] frame for image export: lit pixels and
+/// the sprite amber, dark pixels dim and undrawn pixels black.
+#[must_use]
+pub fn frame_colour(cell: char) -> Rgb {
+    match cell {
+        '#' => Rgb::new(255, 176, 0),
+        '.' => Rgb::new(45, 30, 5),
+        _ => Rgb::BLACK,
+    }
+}
+
+/// The solution: the sum of the signal strengths, then the letters on the
+/// screen.
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const SOLVED_WHILE_PARSING: bool = true;
+    type Input = Crt;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Self::parse_reader(input.as_bytes())
+    }
+
+    fn parse_reader(input: impl BufRead) -> aoc::Result<Self::Input> {
+        Ok(run_program(input)?)
+    }
+
+    fn part_a(crt: &Self::Input) -> aoc::Result<Answer> {
+        Ok(crt.signal_strength().into())
+    }
+
+    fn part_b(crt: &Self::Input) -> aoc::Result<Answer> {
+        Ok(crt.screen().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE_A: &str = "addx 15\naddx -11\naddx 6\naddx -3\naddx 5\naddx -1\naddx -8\naddx 13\naddx 4\nnoop\naddx -1\naddx 5\naddx -1\naddx 5\naddx -1\naddx 5\naddx -1\naddx 5\naddx -1\naddx -35\naddx 1\naddx 24\naddx -19\naddx 1\naddx 16\naddx -11\nnoop\nnoop\naddx 21\naddx -15\nnoop\nnoop\naddx -3\naddx 9\naddx 1\naddx -3\naddx 8\naddx 1\naddx 5\nnoop\nnoop\nnoop\nnoop\nnoop\naddx -36\nnoop\naddx 1\naddx 7\nnoop\nnoop\nnoop\naddx 2\naddx 6\nnoop\nnoop\nnoop\nnoop\nnoop\naddx 1\nnoop\nnoop\naddx 7\naddx 1\nnoop\naddx -13\naddx 13\naddx 7\nnoop\naddx 1\naddx -33\nnoop\nnoop\nnoop\naddx 2\nnoop\nnoop\nnoop\naddx 8\nnoop\naddx -1\naddx 2\naddx 1\nnoop\naddx 17\naddx -9\naddx 1\naddx 1\naddx -3\naddx 11\nnoop\nnoop\naddx 1\nnoop\naddx 1\nnoop\nnoop\naddx -13\naddx -19\naddx 1\naddx 3\naddx 26\naddx -30\naddx 12\naddx -1\naddx 3\naddx 1\nnoop\nnoop\nnoop\naddx -9\naddx 18\naddx 1\naddx 2\nnoop\nnoop\naddx 9\nnoop\nnoop\nnoop\naddx -1\naddx 2\naddx -37\naddx 1\naddx 3\nnoop\naddx 15\naddx -21\naddx 22\naddx -6\naddx 1\nnoop\naddx 2\naddx 1\nnoop\naddx -10\nnoop\nnoop\naddx 20\naddx 1\naddx 2\naddx 2\naddx -6\naddx -11\nnoop\nnoop\nnoop\n";
+
+    #[test]
+    fn invalid_instructions() {
+        let err = parse_instructions("noop\naddx 1\naddx").err().unwrap();
+        assert_eq!(err.position(), Some((3, 5)));
+
+        let err = parse_instructions("noop\nmulx 2").err().unwrap();
+        assert_eq!(err.position(), Some((2, 1)));
+
+        let err = parse_instructions("addx +-1").err().unwrap();
+        assert_eq!(err.position(), Some((1, 6)));
+    }
+
+    /// Builds `addx n`, or `noop` without an operand.
+    fn instruction(n: Option<isize>) -> Box<dyn CPUInstruction> {
+        match n {
+            Some(n) => Box::new(Addx { n }),
+            None => Box::new(Nop {}),
+        }
+    }
+
+    proptest! {
+        // Instructions are trait objects, so they are compared through their
+        // derived `Debug` output, which doesn't depend on `Display`.
+        #[test]
+        fn instruction_round_trip(n in prop::option::of(any::<isize>())) {
+            let instr = instruction(n);
+            let parsed = instr.to_string().parse::<Box<dyn CPUInstruction>>().unwrap();
+            prop_assert_eq!(format!("{parsed:?}"), format!("{instr:?}"));
+        }
+    }
+
+    #[test]
+    fn display_example() {
+        let instructions = parse_instructions(EXAMPLE_A).unwrap();
+        let text = instructions
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(text.join("\n"), EXAMPLE_A.trim_end());
+    }
+
+    #[test]
+    fn test_sum() {
+        let instructions = parse_instructions(EXAMPLE_A).unwrap();
+
+        assert_eq!(calc_sum(&instructions), 13140);
+    }
+
+    #[test]
+    fn extreme_addx() {
+        let program = format!("addx {}\n{}", isize::MIN, "noop\n".repeat(40));
+        let crt = run_cpu(&parse_instructions(&program).unwrap());
+        assert_eq!(
+            crt.screen().to_string().lines().next(),
+            Some("##......................................")
+        );
+
+        let program = format!("addx {}\n{}", isize::MAX, "noop\n".repeat(40));
+        let crt = run_cpu(&parse_instructions(&program).unwrap());
+        assert!(!crt.screen().to_string().lines().next().unwrap()[2..].contains('#'));
+    }
+
+    #[test]
+    fn device_frames() {
+        let mut device = Device::new(parse_instructions(EXAMPLE_A).unwrap());
+        for _ in 0..3 {
+            assert!(device.step());
+        }
+        let frame = device.frame();
+        assert_eq!(
+            frame.caption,
+            "cycle 3: X = 16, signal strength 0, running `addx -11`"
+        );
+        assert_eq!(frame.cells.row(0)[..5], ['#', '#', '.', ' ', ' ']);
+
+        while device.step() {}
+        let frame = device.frame();
+        assert!(frame
+            .caption
+            .ends_with("signal strength 13140, running `noop`"));
+        let screen = frame.cells.rows().take(6).flatten().collect::<String>();
+        assert_eq!(
+            screen,
+            draw_screen(&device.instrs).iter().collect::<String>()
+        );
+    }
+
+    #[test]
+    fn extreme_device_frames() {
+        let program = format!("addx {}\naddx -1\nnoop\nnoop", -isize::MAX);
+        let mut device = Device::new(parse_instructions(&program).unwrap());
+        while device.step() {
+            let frame = device.frame();
+            assert!(frame.caption.starts_with("cycle "));
+        }
+        let frame = device.frame();
+        assert!(frame.caption.contains(&format!("X = {}", isize::MIN + 1)));
+        assert!(!frame.cells.row(Crt::HEIGHT + 1).contains(&'#'));
+    }
+
+    #[test]
+    fn streamed() {
+        let crt = run_program(EXAMPLE_A.as_bytes()).unwrap();
+        let instructions = parse_instructions(EXAMPLE_A).unwrap();
+        assert_eq!(crt, run_cpu(&instructions));
+
+        let long = EXAMPLE_A.repeat(3);
+        assert_eq!(run_program(long.as_bytes()).unwrap().pixels.len(), 240);
+    }
+
+    #[test]
+    fn test_screen() {
+        let instructions = parse_instructions(EXAMPLE_A).unwrap();
+
+        assert_eq!(draw_screen(&instructions).iter().collect::<String>(), "##..##..##..##..##..##..##..##..##..##..###...###...###...###...###...###...###.####....####....####....####....####....#####.....#####.....#####.....#####.....######......######......######......###########.......#######.......#######.....");
+    }
+}
@@ -0,0 +1,417 @@
+#![warn(clippy::pedantic)]
+#![warn(missing_docs)]
+
+//! Day 11: Monkey in the Middle, monkeys throwing items around.
+
+use aoc::parse::{self, blocks};
+use aoc::{Answer, ParseError, Solution};
+use itertools::Itertools;
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+
+/// How a monkey changes the worry level of an item it inspects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Op {
+    /// `new = old + n`
+    Add(usize),
+    /// `new = old * n`
+    Mul(usize),
+    /// `new = old * old`
+    Square,
+}
+
+impl Op {
+    /// Returns the new worry level of an item at `old`, or `None` if it
+    /// overflows.
+    #[must_use]
+    pub fn apply(&self, old: usize) -> Option<usize> {
+        match *self {
+            Op::Add(n) => old.checked_add(n),
+            Op::Mul(n) => old.checked_mul(n),
+            Op::Square => old.checked_mul(old),
+        }
+    }
+}
+
+/// A monkey's notes, and how many items it has inspected so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Monkey {
+    /// Number of the monkey in its notes.
+    pub id: usize,
+    /// Worry levels of the items it holds, in the order it inspects them.
+    pub items: VecDeque<usize>,
+    /// How inspecting an item changes its worry level.
+    pub op: Op,
+    /// Divisor deciding where an item is thrown, never 0.
+    pub test_div: usize,
+    /// Index of the monkey an item is thrown to when divisible.
+    pub target_true: usize,
+    /// Index of the monkey an item is thrown to otherwise.
+    pub target_false: usize,
+    /// Number of items inspected so far.
+    pub n_inspected: usize,
+}
+
+impl FromStr for Op {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Op, ParseError> {
+        if s == "new = old * old" {
+            Ok(Op::Square)
+        } else if let Some(val) = s.strip_prefix("new = old * ") {
+            Ok(Op::Mul(parse::token(s, val)?))
+        } else if let Some(val) = s.strip_prefix("new = old + ") {
+            Ok(Op::Add(parse::token(s, val)?))
+        } else {
+            Err(ParseError::line("expected `new = old <+|*> <n>`", s))
+        }
+    }
+}
+
+/// Returns the rest of `line` after its leading whitespace and `prefix`.
+fn field<'a>(line: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    line.trim_start()
+        .strip_prefix(prefix)
+        .ok_or_else(|| ParseError::line(format!("expected `{prefix}...`"), line))
+}
+
+impl FromStr for Monkey {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Monkey, ParseError> {
+        let (line_id, line_items, line_op, line_test, line_true, line_false) = s
+            .lines()
+            .collect_tuple::<_>()
+            .ok_or_else(|| ParseError::input("expected six lines per monkey"))?;
+
+        let id = field(line_id, "Monkey ")?
+            .strip_suffix(':')
+            .ok_or_else(|| ParseError::line("expected `Monkey <n>:`", line_id))?;
+        let items = field(line_items, "Starting items:")
+            .map_err(|e| e.shift_lines(1))?
+            .trim_start();
+        let op = field(line_op, "Operation: ").map_err(|e| e.shift_lines(1))?;
+        Ok(Monkey {
+            id: parse::token(line_id, id)?,
+            items: items
+                .split(", ")
+                .filter(|item| !item.is_empty())
+                .map(|item| parse::token(line_items, item))
+                .collect::<Result<_, _>>()
+                .map_err(|e| e.shift_lines(1))?,
+            op: Op::from_str(op).map_err(|e| e.within(line_op, op).shift_lines(2))?,
+            test_div: field(line_test, "Test: divisible by ")
+                .and_then(|n| match parse::token(line_test, n)? {
+                    0 => Err(ParseError::new(
+                        "cannot test divisibility by 0",
+                        line_test,
+                        n,
+                    )),
+                    d => Ok(d),
+                })
+                .map_err(|e| e.shift_lines(3))?,
+            target_true: field(line_true, "If true: throw to monkey ")
+                .and_then(|n| parse::token(line_true, n))
+                .map_err(|e| e.shift_lines(4))?,
+            target_false: field(line_false, "If false: throw to monkey ")
+                .and_then(|n| parse::token(line_false, n))
+                .map_err(|e| e.shift_lines(5))?,
+            n_inspected: 0,
+        })
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Op::Add(n) => write!(f, "new = old + {n}"),
+            Op::Mul(n) => write!(f, "new = old * {n}"),
+            Op::Square => write!(f, "new = old * old"),
+        }
+    }
+}
+
+impl fmt::Display for Monkey {
+    /// Writes the monkey's notes as they appear in the puzzle input. The
+    /// inspection count isn't part of them.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Monkey {}:", self.id)?;
+        writeln!(f, "  Starting items: {}", self.items.iter().join(", "))?;
+        writeln!(f, "  Operation: {}", self.op)?;
+        writeln!(f, "  Test: divisible by {}", self.test_div)?;
+        writeln!(f, "    If true: throw to monkey {}", self.target_true)?;
+        write!(f, "    If false: throw to monkey {}", self.target_false)
+    }
+}
+
+/// Parses the monkey definitions, separated by blank lines.
+///
+/// # Errors
+///
+/// Fails on the first monkey that can't be parsed or throws to a monkey that
+/// doesn't exist.
+pub fn parse_monkeys(s: &str) -> Result<Vec<Monkey>, ParseError> {
+    let monkeys = blocks(s)
+        .map(|(start, block)| {
+            Monkey::from_str(block)
+                .map(|m| (start, block, m))
+                .map_err(|e| e.shift_lines(start))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let n_monkeys = monkeys.len();
+    for (start, block, m) in &monkeys {
+        for (i, target) in [(4, m.target_true), (5, m.target_false)] {
+            if target >= n_monkeys {
+                let line = block.lines().nth(i).unwrap_or_default();
+                let span = line.rsplit(' ').next().unwrap_or_default();
+                return Err(ParseError::new(
+                    format!("there is no monkey {target}, only {n_monkeys}"),
+                    line,
+                    span,
+                )
+                .shift_lines(start + i));
+            }
+        }
+    }
+    Ok(monkeys.into_iter().map(|(_, _, m)| m).collect())
+}
+
+/// Lets every monkey in turn inspect and throw all of its items.
+///
+/// # Errors
+///
+/// Fails if a worry level overflows or a monkey throws to one that doesn't
+/// exist.
+pub fn monkey_round<F>(monkeys: &mut [Monkey], worry_update: F) -> Result<(), &'static str>
+where
+    F: Fn(usize) -> usize,
+{
+    for i in 0..monkeys.len() {
+        while let Some(item) = monkeys[i].items.pop_front() {
+            let monkey = &mut monkeys[i];
+            let item = worry_update(monkey.op.apply(item).ok_or("Worry level overflows")?);
+            let target = if item.is_multiple_of(monkey.test_div) {
+                monkey.target_true
+            } else {
+                monkey.target_false
+            };
+
+            monkey.n_inspected += 1;
+            monkeys
+                .get_mut(target)
+                .ok_or("Item thrown to a monkey that doesn't exist")?
+                .items
+                .push_back(item);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the product of all divisors, which keeps worry levels bounded
+/// without changing any of the divisibility tests.
+///
+/// # Errors
+///
+/// Fails if the product overflows.
+pub fn worry_modulo(monkeys: &[Monkey]) -> Result<usize, &'static str> {
+    monkeys
+        .iter()
+        .try_fold(1usize, |product, m| product.checked_mul(m.test_div))
+        .ok_or("Product of the divisors overflows")
+}
+
+fn top2(mut it: impl Iterator<Item = usize>) -> Option<[usize; 2]> {
+    let (a, b) = (it.next()?, it.next()?);
+    let (mut top0, mut top1) = (a.max(b), a.min(b));
+
+    for i in it {
+        if i >= top0 {
+            (top0, top1) = (i, top0);
+        } else if i > top1 {
+            top1 = i;
+        }
+    }
+
+    Some([top0, top1])
+}
+
+/// Returns the product of the two highest inspection counts.
+///
+/// # Errors
+///
+/// Fails if there are fewer than two monkeys or the product overflows.
+pub fn calc_monkey_business_level(monkeys: &[Monkey]) -> Result<usize, &'static str> {
+    let [a, b] = top2(monkeys.iter().map(|m| m.n_inspected)).ok_or("Fewer than two monkeys")?;
+    a.checked_mul(b).ok_or("Level of monkey business overflows")
+}
+
+/// Runs `rounds` rounds and returns the resulting level of monkey business.
+///
+/// # Errors
+///
+/// Fails if a round fails or the level of monkey business can't be computed.
+pub fn simulate_monkeys<F>(
+    mut monkeys: Vec<Monkey>,
+    rounds: usize,
+    worry_update: F,
+) -> Result<usize, &'static str>
+where
+    F: Fn(usize) -> usize,
+{
+    for _ in 0..rounds {
+        monkey_round(&mut monkeys, &worry_update)?;
+    }
+    calc_monkey_business_level(&monkeys)
+}
+
+/// The solution: the level of monkey business after 20 rounds, then after
+/// 10000 rounds with unbounded worry.
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = Vec<Monkey>;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Ok(parse_monkeys(input)?)
+    }
+
+    fn part_a(monkeys: &Self::Input) -> aoc::Result<Answer> {
+        Ok(simulate_monkeys(monkeys.clone(), 20, |w| w / 3)?.into())
+    }
+
+    fn part_b(monkeys: &Self::Input) -> aoc::Result<Answer> {
+        let modulo = worry_modulo(monkeys)?;
+        Ok(simulate_monkeys(monkeys.clone(), 10000, |w| w % modulo)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../sample.txt");
+
+    #[test]
+    fn test_a() {
+        let monkeys = parse_monkeys(EXAMPLE).unwrap();
+        assert_eq!(simulate_monkeys(monkeys, 20, |w| w / 3), Ok(10605));
+    }
+
+    #[test]
+    fn invalid_monkeys() {
+        let input = EXAMPLE.replace("new = old * 19", "new = old * x");
+        assert_eq!(parse_monkeys(&input).unwrap_err().position(), Some((3, 26)));
+
+        let input = EXAMPLE.replace("items: 54, 65, 75, 74", "items: 54, 6x, 75, 74");
+        assert_eq!(parse_monkeys(&input).unwrap_err().position(), Some((9, 23)));
+
+        let input = EXAMPLE.replace("If false: throw to monkey 3", "If false: 3");
+        assert_eq!(parse_monkeys(&input).unwrap_err().position(), Some((6, 1)));
+
+        let input = EXAMPLE.replace("divisible by 13", "divisible by 0");
+        assert_eq!(
+            parse_monkeys(&input).unwrap_err().position(),
+            Some((18, 22))
+        );
+
+        let input = EXAMPLE.replace("If true: throw to monkey 1", "If true: throw to monkey 4");
+        assert_eq!(
+            parse_monkeys(&input).unwrap_err().position(),
+            Some((19, 30))
+        );
+    }
+
+    #[test]
+    fn display_example() {
+        let monkeys = parse_monkeys(EXAMPLE).unwrap();
+        assert_eq!(format!("{}\n", monkeys.iter().join("\n\n")), EXAMPLE);
+    }
+
+    fn ops() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            any::<usize>().prop_map(Op::Add),
+            any::<usize>().prop_map(Op::Mul),
+            Just(Op::Square),
+        ]
+    }
+
+    fn monkeys() -> impl Strategy<Value = Monkey> {
+        (
+            any::<usize>(),
+            prop::collection::vec_deque(any::<usize>(), 0..10),
+            ops(),
+            1..=usize::MAX,
+            any::<usize>(),
+            any::<usize>(),
+        )
+            .prop_map(
+                |(id, items, op, test_div, target_true, target_false)| Monkey {
+                    id,
+                    items,
+                    op,
+                    test_div,
+                    target_true,
+                    target_false,
+                    n_inspected: 0,
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn op_round_trip(op in ops()) {
+            prop_assert_eq!(op.to_string().parse::<Op>().unwrap(), op);
+        }
+
+        #[test]
+        fn monkey_round_trip(monkey in monkeys()) {
+            prop_assert_eq!(monkey.to_string().parse::<Monkey>().unwrap(), monkey);
+        }
+
+        #[test]
+        fn monkeys_round_trip(monkeys in prop::collection::vec(monkeys(), 1..5)) {
+            let n = monkeys.len();
+            let monkeys = monkeys
+                .into_iter()
+                .map(|m| Monkey {
+                    target_true: m.target_true % n,
+                    target_false: m.target_false % n,
+                    ..m
+                })
+                .collect::<Vec<_>>();
+            let text = monkeys.iter().join("\n\n");
+            prop_assert_eq!(parse_monkeys(&text).unwrap(), monkeys);
+        }
+    }
+
+    #[test]
+    fn test_b() {
+        let monkeys = parse_monkeys(EXAMPLE).unwrap();
+        let modulo = worry_modulo(&monkeys).unwrap();
+        assert_eq!(
+            simulate_monkeys(monkeys, 10000, |w| w % modulo),
+            Ok(2_713_310_158)
+        );
+    }
+
+    #[test]
+    fn impossible_simulations() {
+        let monkeys = parse_monkeys(EXAMPLE).unwrap();
+        assert!(simulate_monkeys(monkeys.clone(), 10000, |w| w).is_err());
+        assert!(simulate_monkeys(monkeys[..1].to_vec(), 20, |w| w / 3).is_err());
+
+        let mut thrown_away = monkeys[..2].to_vec();
+        thrown_away[0].target_false = 2;
+        assert!(simulate_monkeys(thrown_away, 1, |w| w / 3).is_err());
+    }
+
+    #[test]
+    fn top_two_in_any_order() {
+        assert_eq!(top2([1, 5, 3].into_iter()), Some([5, 3]));
+        assert_eq!(top2([2].into_iter()), None);
+    }
+}
@@ -0,0 +1,47 @@
+use aoc::Solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use std::path::Path;
+
+/// Benchmarks parsing and both parts of `S` on the day's real input, only
+/// parsing for days solved while parsing.
+fn bench_day<S: Solution>(c: &mut Criterion) {
+    let name = format!("day{:02}", S::DAY);
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(&name)
+        .join("input.txt");
+    let text = aoc::read_input(path).unwrap();
+    let input = S::parse(&text).unwrap();
+
+    let mut group = c.benchmark_group(name);
+    group.sample_size(20);
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&text)).unwrap()));
+    if S::SOLVED_WHILE_PARSING {
+        group.finish();
+        return;
+    }
+    group.bench_function("part_a", |b| {
+        b.iter(|| S::part_a(black_box(&input)).unwrap())
+    });
+    group.bench_function("part_b", |b| {
+        b.iter(|| S::part_b(black_box(&input)).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(
+    days,
+    bench_day::<day01::Day01>,
+    bench_day::<day02::Day02>,
+    bench_day::<day03::Day03>,
+    bench_day::<day04::Day04>,
+    bench_day::<day05::Day05>,
+    bench_day::<day06::Day06>,
+    bench_day::<day07::Day07>,
+    bench_day::<day08::Day08>,
+    bench_day::<day09::Day09>,
+    bench_day::<day10::Day10>,
+    bench_day::<day11::Day11>,
+);
+criterion_main!(days);
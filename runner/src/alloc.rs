@@ -0,0 +1,151 @@
+//! Allocation counting for the `--alloc` report of the runner.
+//!
+//! [`Counting`] wraps the system allocator and, once [`enable`]d, counts
+//! allocations and tracks the bytes in use. The `aoc` binary installs it as
+//! its global allocator; elsewhere [`measure`] reports nothing.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering::Relaxed};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+/// Bytes allocated minus bytes freed since counting was enabled, negative
+/// after freeing memory allocated before.
+static LIVE: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+
+/// The system allocator, counting what goes through it while enabled.
+pub struct Counting;
+
+fn allocated(size: usize) {
+    if ENABLED.load(Relaxed) {
+        COUNT.fetch_add(1, Relaxed);
+        BYTES.fetch_add(size, Relaxed);
+        let live = LIVE.fetch_add(size.cast_signed(), Relaxed) + size.cast_signed();
+        PEAK.fetch_max(live, Relaxed);
+    }
+}
+
+fn freed(size: usize) {
+    if ENABLED.load(Relaxed) {
+        LIVE.fetch_sub(size.cast_signed(), Relaxed);
+    }
+}
+
+// SAFETY: every call is forwarded unchanged to the system allocator, the
+// counters only look at the sizes.
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        freed(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new.is_null() {
+            freed(layout.size());
+            allocated(new_size);
+        }
+        new
+    }
+}
+
+/// Starts counting allocations. Counts are global, so measurements of code
+/// running in several threads at once mix.
+pub fn enable() {
+    ENABLED.store(true, Relaxed);
+}
+
+/// Allocations made while running some code.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Allocations {
+    /// Number of allocations, a reallocation counting as one.
+    pub count: usize,
+    /// Total bytes allocated.
+    pub bytes: usize,
+    /// Most bytes in use at once beyond those in use at the start.
+    pub peak: usize,
+}
+
+/// Runs `f` and returns its result with the allocations it made, all zero
+/// unless [`Counting`] is the global allocator and counting is enabled.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Allocations) {
+    let (count, bytes) = (COUNT.load(Relaxed), BYTES.load(Relaxed));
+    let live = LIVE.load(Relaxed);
+    PEAK.store(live, Relaxed);
+    let result = f();
+    let allocations = Allocations {
+        count: COUNT.load(Relaxed) - count,
+        bytes: BYTES.load(Relaxed) - bytes,
+        peak: (PEAK.load(Relaxed) - live).max(0).unsigned_abs(),
+    };
+    (result, allocations)
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`.
+#[must_use]
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    // Precision doesn't matter for display.
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    #[test]
+    fn counted_allocations() {
+        enable();
+        let (sum, allocations) = measure(|| {
+            let big = vec![1u8; 1 << 20];
+            let small = vec![2u8; 1000];
+            drop(big);
+            let mut grown = Vec::<u8>::with_capacity(10);
+            grown.extend([3; 100]);
+            small.len() + grown.len()
+        });
+        assert_eq!(sum, 1100);
+        // Other tests running at the same time may allocate as well.
+        assert!(allocations.count >= 4, "{allocations:?}");
+        assert!(allocations.bytes >= (1 << 20) + 1110, "{allocations:?}");
+        assert!(allocations.peak >= (1 << 20), "{allocations:?}");
+    }
+
+    #[test]
+    fn byte_units() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 << 30), "3.0 GiB");
+    }
+}
@@ -0,0 +1,319 @@
+//! Checks computed answers against the expected ones in each day's
+//! `answers.txt`, and against the answers computed by the previous check.
+
+use crate::days::Day;
+use aoc::answers::{format_answers, load_answers, parse_answers, Expected};
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Outcome of one entry of a day's answers file.
+#[derive(Debug)]
+pub struct Checked {
+    pub day: u8,
+    pub expected: Expected,
+    /// Computed answer, or the error that occurred instead.
+    pub actual: Result<String, String>,
+}
+
+impl Checked {
+    /// Returns whether the computed answer is the expected one.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.actual.as_ref() == Ok(&self.expected.answer)
+    }
+}
+
+/// Solves every entry of the day's `answers.txt`, reading each input once.
+///
+/// # Errors
+///
+/// Fails if the answers file or one of the inputs it refers to can't be read.
+pub fn check_day(day: &Day) -> aoc::Result<Vec<Checked>> {
+    check_answers(day, &day.dir(), &load_answers(&day.dir())?)
+}
+
+/// Solves the `expected` entries with the inputs in `dir`, reading each input
+/// once even if its entries aren't next to each other.
+///
+/// If solving the parts of an input together fails, each part is solved
+/// again on its own, so that only the parts that fail get the error.
+fn check_answers(day: &Day, dir: &Path, expected: &[Expected]) -> aoc::Result<Vec<Checked>> {
+    let mut checked = vec![];
+
+    let mut seen = HashSet::new();
+    let inputs = expected
+        .iter()
+        .map(|e| e.input.as_str())
+        .filter(|&input| seen.insert(input))
+        .collect::<Vec<_>>();
+    for input in inputs {
+        let entries = expected
+            .iter()
+            .filter(|e| e.input == input)
+            .collect::<Vec<_>>();
+        let parts = entries.iter().map(|e| e.part).collect::<Vec<_>>();
+        let text = aoc::read_input(dir.join(format!("{input}.txt")))?;
+
+        let actual = match day.solve(text.as_bytes(), &parts) {
+            Ok(solved) => solved
+                .parts
+                .into_iter()
+                .map(|p| Ok(p.answer.to_string()))
+                .collect(),
+            Err(_) => parts
+                .iter()
+                .map(|&part| match day.solve(text.as_bytes(), &[part]) {
+                    Ok(solved) => Ok(solved.parts[0].answer.to_string()),
+                    Err(e) => Err(e.to_string()),
+                })
+                .collect::<Vec<_>>(),
+        };
+        for (entry, actual) in entries.into_iter().zip(actual) {
+            checked.push(Checked {
+                day: day.day,
+                expected: entry.clone(),
+                actual,
+            });
+        }
+    }
+
+    Ok(checked)
+}
+
+/// Shows how `new` differs from `old`, each headed by its label.
+///
+/// Single-line answers are shown one below the other. Multi-line answers,
+/// like the screen of day 10, are shown side by side with a third column
+/// marking every character that differs with `x`.
+#[must_use]
+pub fn diff(labels: [&str; 2], old: &str, new: &str) -> String {
+    let mut text = String::new();
+    if !old.contains('\n') && !new.contains('\n') {
+        let width = labels[0].len().max(labels[1].len()) + 1;
+        for (label, answer) in labels.into_iter().zip([old, new]) {
+            writeln!(text, "  {:<width$} {answer}", format!("{label}:")).unwrap();
+        }
+        return text;
+    }
+
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+    let width = |lines: &[&str], label: &str| {
+        lines
+            .iter()
+            .map(|l| l.chars().count())
+            .chain([label.len()])
+            .max()
+            .unwrap_or(0)
+    };
+    let (w0, w1) = (width(&old, labels[0]), width(&new, labels[1]));
+    writeln!(text, "  {:<w0$}  {:<w1$}  diff", labels[0], labels[1]).unwrap();
+    let mut differing = 0;
+    for i in 0..old.len().max(new.len()) {
+        let (a, b) = (old.get(i).copied(), new.get(i).copied());
+        let (a, b) = (a.unwrap_or_default(), b.unwrap_or_default());
+        let (mut xs, mut ys) = (a.chars(), b.chars());
+        let marks = (0..w0.max(w1))
+            .map(|_| match (xs.next(), ys.next()) {
+                (x, y) if x == y => ' ',
+                _ => 'x',
+            })
+            .collect::<String>();
+        differing += marks.matches('x').count();
+        let line = format!("  {a:<w0$}  {b:<w1$}  {marks}");
+        writeln!(text, "{}", line.trim_end()).unwrap();
+    }
+    writeln!(text, "  characters that differ: {differing}").unwrap();
+    text
+}
+
+/// Answers computed by earlier checks, kept in one file per day in the
+/// format of `answers.txt`.
+pub struct History {
+    dir: PathBuf,
+}
+
+impl History {
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> History {
+        History { dir: dir.into() }
+    }
+
+    /// Returns the `.aoc-check` directory at the root of the workspace.
+    #[must_use]
+    pub fn default_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(".aoc-check")
+    }
+
+    fn path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{day:02}.txt"))
+    }
+
+    /// Returns the answers recorded for `day`, none if it was never checked.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the recorded answers can't be read or parsed.
+    pub fn load(&self, day: u8) -> aoc::Result<Vec<Expected>> {
+        let path = self.path(day);
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+        parse_answers(&aoc::read_input(&path)?)
+            .map_err(|e| format!("{}: {e}", path.display()).into())
+    }
+
+    /// Records the answers computed for `day`, keeping the earlier ones of
+    /// entries that failed with an error.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file can't be written.
+    pub fn record(&self, day: u8, previous: &[Expected], checked: &[Checked]) -> aoc::Result<()> {
+        let entries = checked
+            .iter()
+            .filter_map(|c| match &c.actual {
+                Ok(answer) => Some(Expected {
+                    answer: answer.clone(),
+                    ..c.expected.clone()
+                }),
+                Err(_) => find(previous, &c.expected).cloned(),
+            })
+            .collect::<Vec<_>>();
+        let path = self.path(day);
+        let text = format!(
+            "# Answers computed by the last `aoc check`, see aoc::answers.\n{}",
+            format_answers(&entries)
+        );
+        fs::create_dir_all(&self.dir)
+            .and_then(|()| fs::write(&path, text))
+            .map_err(|e| format!("Failed to write {}: {e}", path.display()).into())
+    }
+}
+
+fn find<'a>(entries: &'a [Expected], entry: &Expected) -> Option<&'a Expected> {
+    entries
+        .iter()
+        .find(|e| e.input == entry.input && e.part == entry.part)
+}
+
+/// Returns the answer recorded by the previous check if the computed one
+/// differs from it.
+#[must_use]
+pub fn changed<'a>(previous: &'a [Expected], checked: &Checked) -> Option<&'a str> {
+    let answer = checked.actual.as_ref().ok()?;
+    find(previous, &checked.expected)
+        .map(|e| e.answer.as_str())
+        .filter(|&before| before != answer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+    use aoc::Part;
+
+    fn checked(part: Part, answer: &str, actual: Result<&str, &str>) -> Checked {
+        Checked {
+            day: 10,
+            expected: Expected {
+                input: "sample".to_string(),
+                part,
+                answer: answer.to_string(),
+            },
+            actual: actual.map(str::to_string).map_err(str::to_string),
+        }
+    }
+
+    #[test]
+    fn answers_of_a_day_pass() {
+        let checked = check_day(days::find(1).unwrap()).unwrap();
+        assert_eq!(checked.len(), 4);
+        assert!(checked.iter().all(Checked::passed));
+    }
+
+    #[test]
+    fn interleaved_inputs() {
+        let day = days::find(1).unwrap();
+        let answers = "sample a: 24000\ninput a: 69501\nsample b: 45000\n";
+        let checked = check_answers(day, &day.dir(), &parse_answers(answers).unwrap()).unwrap();
+        let entries = checked
+            .iter()
+            .map(|c| (c.expected.input.as_str(), c.expected.part, c.passed()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entries,
+            [
+                ("sample", Part::A, true),
+                ("sample", Part::B, true),
+                ("input", Part::A, true)
+            ]
+        );
+    }
+
+    #[test]
+    fn one_failing_part() {
+        let dir = std::env::temp_dir().join(format!("aoc-check-parts-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("two.txt"), "1\n\n2\n").unwrap();
+        let answers = parse_answers("two a: 2\ntwo b: 3\n").unwrap();
+        let checked = check_answers(days::find(1).unwrap(), &dir, &answers).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(checked[0].passed());
+        assert_eq!(
+            checked[1].actual,
+            Err("Fewer than three elves in input".to_string())
+        );
+    }
+
+    #[test]
+    fn screen_diff() {
+        let text = diff(["expected", "actual"], "#.#\n.#.", "#..\n.#.\n#");
+        assert_eq!(
+            text,
+            "  expected  actual  diff
+  #.#       #..       x
+  .#.       .#.
+            #       x
+  characters that differ: 2
+"
+        );
+        assert_eq!(
+            diff(["expected", "last check"], "24000", "24001"),
+            "  expected:   24000\n  last check: 24001\n"
+        );
+    }
+
+    #[test]
+    fn recorded_answers() {
+        let dir = std::env::temp_dir().join(format!("aoc-check-{}", std::process::id()));
+        let history = History::new(&dir);
+        assert!(history.load(10).unwrap().is_empty());
+
+        let first = [
+            checked(Part::A, "1", Ok("1")),
+            checked(Part::B, "#.\n.#", Ok("#.\n.#")),
+        ];
+        history.record(10, &[], &first).unwrap();
+        let previous = history.load(10).unwrap();
+        assert!(first.iter().all(|c| changed(&previous, c).is_none()));
+
+        let second = [
+            checked(Part::A, "1", Ok("2")),
+            checked(Part::B, "#.\n.#", Err("failed")),
+        ];
+        assert_eq!(changed(&previous, &second[0]), Some("1"));
+        assert_eq!(changed(&previous, &second[1]), None);
+        history.record(10, &previous, &second).unwrap();
+        let recorded = history.load(10).unwrap();
+        assert_eq!(recorded[0].answer, "2");
+        assert_eq!(recorded[1].answer, "#.\n.#");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
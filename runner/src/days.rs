@@ -0,0 +1,133 @@
+use crate::alloc::{self, Allocations};
+use aoc::{Answer, Part, Solution};
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Type-erased entry point for a single day.
+pub struct Day {
+    pub day: u8,
+    solve: fn(&mut dyn BufRead, &[Part]) -> aoc::Result<Solved>,
+}
+
+/// Answer to one part together with the time it took to compute.
+#[derive(Debug)]
+pub struct PartResult {
+    pub part: Part,
+    pub answer: Answer,
+    pub elapsed: Duration,
+    /// Allocations made computing the answer, see [`alloc`].
+    pub allocations: Allocations,
+}
+
+/// Results of solving some parts of a day's puzzle.
+#[derive(Debug)]
+pub struct Solved {
+    pub parse_time: Duration,
+    /// Allocations made parsing the input, see [`alloc`].
+    pub parse_allocations: Allocations,
+    /// Whether the answers were computed while parsing, see
+    /// [`Solution::SOLVED_WHILE_PARSING`].
+    pub solved_while_parsing: bool,
+    pub parts: Vec<PartResult>,
+}
+
+fn solve<S: Solution>(input: &mut dyn BufRead, parts: &[Part]) -> aoc::Result<Solved> {
+    let start = Instant::now();
+    let (input, parse_allocations) = alloc::measure(|| S::parse_reader(input));
+    let input = input?;
+    let parse_time = start.elapsed();
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let (answer, allocations) = alloc::measure(|| S::solve(&input, part));
+            Ok(PartResult {
+                part,
+                answer: answer?,
+                elapsed: start.elapsed(),
+                allocations,
+            })
+        })
+        .collect::<aoc::Result<_>>()?;
+
+    Ok(Solved {
+        parse_time,
+        parse_allocations,
+        solved_while_parsing: S::SOLVED_WHILE_PARSING,
+        parts,
+    })
+}
+
+impl Day {
+    const fn of<S: Solution>() -> Day {
+        Day {
+            day: S::DAY,
+            solve: solve::<S>,
+        }
+    }
+
+    /// Reads and parses `input` once and solves each of `parts`.
+    ///
+    /// Pass `text.as_bytes()` to solve an input that is already in memory.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the input can't be read or parsed or a part can't be solved.
+    pub fn solve(&self, mut input: impl BufRead, parts: &[Part]) -> aoc::Result<Solved> {
+        (self.solve)(&mut input, parts)
+    }
+
+    /// Directory of the day's crate inside the workspace.
+    #[must_use]
+    pub fn dir(&self) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(format!("day{:02}", self.day))
+    }
+}
+
+pub const DAYS: [Day; 11] = [
+    Day::of::<day01::Day01>(),
+    Day::of::<day02::Day02>(),
+    Day::of::<day03::Day03>(),
+    Day::of::<day04::Day04>(),
+    Day::of::<day05::Day05>(),
+    Day::of::<day06::Day06>(),
+    Day::of::<day07::Day07>(),
+    Day::of::<day08::Day08>(),
+    Day::of::<day09::Day09>(),
+    Day::of::<day10::Day10>(),
+    Day::of::<day11::Day11>(),
+];
+
+/// Looks up the entry for `day`.
+///
+/// # Errors
+///
+/// Fails if there is no solution for `day`.
+pub fn find(day: u8) -> aoc::Result<&'static Day> {
+    DAYS.iter()
+        .find(|d| d.day == day)
+        .ok_or_else(|| format!("No solution for day {day}").into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_in_order() {
+        assert!(DAYS
+            .iter()
+            .enumerate()
+            .all(|(i, d)| usize::from(d.day) == i + 1));
+    }
+
+    #[test]
+    fn unknown_day() {
+        assert!(find(7).is_ok());
+        assert!(find(26).is_err());
+    }
+}
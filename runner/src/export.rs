@@ -0,0 +1,251 @@
+//! Pictures of the state of the days that have one, written as images,
+//! numbered frame sequences or animated GIFs.
+
+use crate::visualize;
+use aoc::export::{Format, GifWriter, Rgb};
+use aoc::geom::Point;
+use aoc::grid::Grid;
+use aoc::visual::{Frame, Visualize};
+use aoc::Part;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use std::time::Duration;
+
+/// How a day's state becomes a picture.
+enum Source {
+    /// A single picture of the parsed input.
+    Still(fn(&str) -> aoc::Result<Grid<Rgb>>),
+    /// The frames of the day's [`visualize`] simulation, coloured cell by
+    /// cell with the day's palette.
+    Frames(fn(char) -> Rgb),
+}
+
+/// Draws the pictures of one day.
+pub struct Exporter {
+    pub day: u8,
+    source: Source,
+}
+
+/// Settings of an export.
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    /// Width and height in pixels of every cell.
+    pub scale: usize,
+    /// Keep only every `every`th step of an animation, and always the last.
+    pub every: usize,
+    /// Time every frame of a GIF is shown.
+    pub delay: Duration,
+    /// Write every kept step as a numbered image, not just the final state.
+    pub frames: bool,
+}
+
+/// Passes the initial frame of `sim` and every `every`th step after it to
+/// `f`, followed by the final frame. Returns the number of frames passed.
+fn kept_frames(
+    sim: &mut dyn Visualize,
+    every: usize,
+    mut f: impl FnMut(Frame) -> aoc::Result<()>,
+) -> aoc::Result<usize> {
+    f(sim.frame())?;
+    let (mut steps, mut kept) = (0, 1);
+    while sim.step() {
+        steps += 1;
+        if steps % every == 0 {
+            f(sim.frame())?;
+            kept += 1;
+        }
+    }
+    if steps % every != 0 {
+        f(sim.frame())?;
+        kept += 1;
+    }
+    Ok(kept)
+}
+
+impl Exporter {
+    /// Writes the pictures of `part` for `input` to `path`, in the format
+    /// its extension asks for, and returns the number of files written.
+    ///
+    /// A `.gif` shows every kept step of the day's simulation, and with
+    /// [`Options::frames`] they are written as numbered `.ppm` or `.png`
+    /// files. Otherwise only the final state is drawn. All frames of an
+    /// animation share a canvas covering the area of every frame.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the input can't be parsed, the day has no animation but one
+    /// is asked for, or a file can't be written.
+    pub fn export(
+        &self,
+        input: &str,
+        part: Part,
+        path: &Path,
+        options: &Options,
+    ) -> aoc::Result<usize> {
+        let format = Format::from_path(path)?;
+        if options.frames && format == Format::Gif {
+            return Err("Numbered frames are written as .ppm or .png, not .gif".into());
+        }
+        let colour = match self.source {
+            Source::Still(_) if options.frames => {
+                return Err(format!("Day {} has no animation", self.day).into());
+            }
+            Source::Still(draw) => {
+                aoc::export::save(&draw(input)?, path, options.scale)?;
+                return Ok(1);
+            }
+            Source::Frames(colour) => colour,
+        };
+        let build = || visualize::find(self.day)?.build(input, part);
+
+        if format != Format::Gif && !options.frames {
+            let mut sim = build()?;
+            while sim.step() {}
+            aoc::export::save(&sim.frame().cells.map(colour), path, options.scale)?;
+            return Ok(1);
+        }
+
+        // A first run of the simulation finds the area all frames cover.
+        let (mut min, mut max) = (None::<Point>, None::<Point>);
+        kept_frames(build()?.as_mut(), options.every, |frame| {
+            let size = Point::new(
+                frame.cells.width().cast_signed(),
+                frame.cells.height().cast_signed(),
+            );
+            let end = frame.origin + size;
+            min = Some(min.map_or(frame.origin, |m| {
+                Point::new(m.x.min(frame.origin.x), m.y.min(frame.origin.y))
+            }));
+            max = Some(max.map_or(end, |m| Point::new(m.x.max(end.x), m.y.max(end.y))));
+            Ok(())
+        })?;
+        let min = min.unwrap_or_default();
+        let size = max.unwrap_or_default() - min;
+        let (width, height) = (size.x.unsigned_abs(), size.y.unsigned_abs());
+        let canvas = |frame: Frame| {
+            let image = frame.cells.map(colour);
+            aoc::export::place(&image, frame.origin - min, width, height, colour(' '))
+        };
+
+        let mut sim = build()?;
+        if format == Format::Gif {
+            let file = File::create(path)
+                .map_err(|e| format!("Failed to create {}: {e}", path.display()))?;
+            let mut gif = GifWriter::new(
+                BufWriter::new(file),
+                width,
+                height,
+                options.scale,
+                options.delay,
+            )?;
+            kept_frames(sim.as_mut(), options.every, |frame| {
+                gif.write_frame(&canvas(frame))
+                    .map_err(|e| format!("Failed to write {}: {e}", path.display()).into())
+            })?;
+            Ok(1)
+        } else {
+            let mut n = 0;
+            kept_frames(sim.as_mut(), options.every, |frame| {
+                aoc::export::save(
+                    &canvas(frame),
+                    &aoc::export::numbered(path, n),
+                    options.scale,
+                )?;
+                n += 1;
+                Ok(())
+            })
+        }
+    }
+}
+
+/// Day 8: the forest, with taller trees lighter and hidden ones darkened.
+fn forest(input: &str) -> aoc::Result<Grid<Rgb>> {
+    Ok(day08::forest_image(&day08::parse_forest(input)?))
+}
+
+pub const EXPORTERS: [Exporter; 3] = [
+    Exporter {
+        day: 8,
+        source: Source::Still(forest),
+    },
+    Exporter {
+        day: 9,
+        source: Source::Frames(day09::frame_colour),
+    },
+    Exporter {
+        day: 10,
+        source: Source::Frames(day10::frame_colour),
+    },
+];
+
+/// Looks up the exporter of `day`.
+///
+/// # Errors
+///
+/// Fails if `day` has no pictures.
+pub fn find(day: u8) -> aoc::Result<&'static Exporter> {
+    EXPORTERS.iter().find(|e| e.day == day).ok_or_else(|| {
+        let days = EXPORTERS.map(|e| e.day.to_string()).join(", ");
+        format!("No pictures of day {day}, only of days {days}").into()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    fn sample(day: u8) -> String {
+        aoc::read_input(days::find(day).unwrap().dir().join("sample.txt")).unwrap()
+    }
+
+    fn options(frames: bool) -> Options {
+        Options {
+            scale: 2,
+            every: 3,
+            delay: Duration::from_millis(20),
+            frames,
+        }
+    }
+
+    #[test]
+    fn exports_of_every_day() {
+        let dir = std::env::temp_dir().join(format!("aoc-export-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        for e in &EXPORTERS {
+            let input = sample(e.day);
+            for name in ["still.ppm", "still.png", "anim.gif"] {
+                let path = dir.join(format!("day{:02}-{name}", e.day));
+                assert_eq!(
+                    e.export(&input, Part::B, &path, &options(false)).unwrap(),
+                    1
+                );
+                assert!(std::fs::metadata(&path).unwrap().len() > 0);
+            }
+        }
+
+        // The 24 steps of the day 9 sample are kept as steps 0, 3, ..., 24.
+        let path = dir.join("rope.png");
+        let written = find(9)
+            .unwrap()
+            .export(&sample(9), Part::A, &path, &options(true));
+        assert_eq!(written.unwrap(), 9);
+        let first = std::fs::read(aoc::export::numbered(&path, 0)).unwrap();
+        let last = std::fs::read(aoc::export::numbered(&path, 8)).unwrap();
+        assert_eq!(first[16..24], last[16..24], "frames of different sizes");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn still_days_have_no_frames() {
+        let path = Path::new("forest.png");
+        let err = find(8)
+            .unwrap()
+            .export(&sample(8), Part::A, path, &options(true));
+        assert!(err.is_err());
+        assert!(find(5).is_err());
+    }
+}
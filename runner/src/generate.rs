@@ -0,0 +1,526 @@
+//! Seeded random puzzle inputs for stress-testing the solutions.
+//!
+//! Every generator produces input in the format of the real puzzle that all
+//! parts can be solved for. What the size means depends on the day, e.g. the
+//! number of elves on day 1 or the side length of the forest on day 8.
+//!
+//! Lines are written as they are generated, so even huge inputs only take
+//! as much memory as the state of the puzzle they describe.
+
+use rand::rngs::StdRng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::{Rng, SeedableRng};
+use std::collections::{HashSet, VecDeque};
+use std::io::{self, Write};
+
+/// Random input generator for a single day.
+pub struct Generator {
+    pub day: u8,
+    /// Size of a typical puzzle input.
+    pub default_size: usize,
+    generate: fn(&mut StdRng, usize, &mut dyn Write) -> io::Result<()>,
+}
+
+impl Generator {
+    /// Writes an input of the given size, or the default size, which only
+    /// depends on `seed`, to `out` one line at a time.
+    ///
+    /// # Errors
+    ///
+    /// Fails if writing to `out` fails.
+    pub fn generate(&self, size: Option<usize>, seed: u64, mut out: impl Write) -> io::Result<()> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (self.generate)(&mut rng, size.unwrap_or(self.default_size), &mut out)
+    }
+}
+
+pub const GENERATORS: [Generator; 11] = [
+    Generator {
+        day: 1,
+        default_size: 250,
+        generate: elves,
+    },
+    Generator {
+        day: 2,
+        default_size: 2500,
+        generate: strategy_guide,
+    },
+    Generator {
+        day: 3,
+        default_size: 300,
+        generate: rucksacks,
+    },
+    Generator {
+        day: 4,
+        default_size: 1000,
+        generate: section_pairs,
+    },
+    Generator {
+        day: 5,
+        default_size: 500,
+        generate: crane_procedure,
+    },
+    Generator {
+        day: 6,
+        default_size: 4096,
+        generate: signal,
+    },
+    Generator {
+        day: 7,
+        default_size: 300,
+        generate: terminal,
+    },
+    Generator {
+        day: 8,
+        default_size: 99,
+        generate: forest,
+    },
+    Generator {
+        day: 9,
+        default_size: 2000,
+        generate: rope_moves,
+    },
+    Generator {
+        day: 10,
+        default_size: 240,
+        generate: program,
+    },
+    Generator {
+        day: 11,
+        default_size: 8,
+        generate: monkeys,
+    },
+];
+
+/// Looks up the generator for `day`.
+///
+/// # Errors
+///
+/// Fails if there is no generator for `day`.
+pub fn find(day: u8) -> aoc::Result<&'static Generator> {
+    GENERATORS
+        .iter()
+        .find(|g| g.day == day)
+        .ok_or_else(|| format!("No input generator for day {day}").into())
+}
+
+/// Returns a random lowercase word of 1 to 8 letters.
+fn word(rng: &mut StdRng) -> String {
+    let len = rng.random_range(1..=8);
+    (0..len).map(|_| rng.random_range('a'..='z')).collect()
+}
+
+/// `size` elves, at least three, carrying up to 15 items each.
+fn elves(rng: &mut StdRng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for elf in 0..size.max(3) {
+        if elf > 0 {
+            writeln!(out)?;
+        }
+        for _ in 0..rng.random_range(1..=15) {
+            writeln!(out, "{}", rng.random_range(1000..=60_000))?;
+        }
+    }
+    Ok(())
+}
+
+/// `size` rounds of rock paper scissors.
+fn strategy_guide(rng: &mut StdRng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..size {
+        let opponent = ['A', 'B', 'C'][rng.random_range(0..3)];
+        let response = ['X', 'Y', 'Z'][rng.random_range(0..3)];
+        writeln!(out, "{opponent} {response}")?;
+    }
+    Ok(())
+}
+
+/// `size` rucksacks rounded up to whole groups of three. Both compartments
+/// of a rucksack share exactly one item type, and each group exactly one
+/// badge.
+fn rucksacks(rng: &mut StdRng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let items = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+
+    for _ in 0..size.div_ceil(3).max(1) {
+        let mut letters = items.clone();
+        letters.shuffle(rng);
+        let badge = letters.pop().expect("52 item types");
+
+        // The remaining 51 item types are split between the three elves, so
+        // nothing but the badge is common to all of them.
+        for pool in letters.chunks(17) {
+            let (shared, only_left, only_right) = (pool[0], &pool[1..9], &pool[9..]);
+            let half = rng.random_range(2..=16);
+            let mut left = vec![shared];
+            let mut right = vec![shared];
+            if rng.random_bool(0.5) {
+                left.push(badge);
+            } else {
+                right.push(badge);
+            }
+            while left.len() < half {
+                left.extend(only_left.choose(rng));
+            }
+            while right.len() < half {
+                right.extend(only_right.choose(rng));
+            }
+            left.shuffle(rng);
+            right.shuffle(rng);
+            writeln!(out, "{}", left.into_iter().chain(right).collect::<String>())?;
+        }
+    }
+    Ok(())
+}
+
+/// `size` pairs of section assignments.
+fn section_pairs(rng: &mut StdRng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut sections = || {
+        let start = rng.random_range(1..=99);
+        (start, rng.random_range(start..=99))
+    };
+    for _ in 0..size {
+        let ((a, b), (c, d)) = (sections(), sections());
+        writeln!(out, "{a}-{b},{c}-{d}")?;
+    }
+    Ok(())
+}
+
+/// Nine stacks of crates followed by `size` moves, at least one, none of
+/// which takes more crates than its stack holds.
+fn crane_procedure(rng: &mut StdRng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut stacks = (0..9)
+        .map(|_| {
+            let height = rng.random_range(1..=8);
+            (0..height)
+                .map(|_| rng.random_range('A'..='Z'))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let max_height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    for level in (0..max_height).rev() {
+        let row = stacks
+            .iter()
+            .map(|stack| {
+                stack
+                    .get(level)
+                    .map_or("   ".to_string(), |c| format!("[{c}]"))
+            })
+            .collect::<Vec<_>>();
+        writeln!(out, "{}", row.join(" "))?;
+    }
+    let numbers = (1..=stacks.len())
+        .map(|i| format!(" {i} "))
+        .collect::<Vec<_>>();
+    writeln!(out, "{}", numbers.join(" "))?;
+    writeln!(out)?;
+
+    // Only the heights matter for the moves to be valid.
+    for _ in 0..size.max(1) {
+        let non_empty = (0..stacks.len())
+            .filter(|&i| !stacks[i].is_empty())
+            .collect::<Vec<_>>();
+        let from = *non_empty.choose(rng).expect("crates are never lost");
+        let to = (from + rng.random_range(1..stacks.len())) % stacks.len();
+        let count = rng.random_range(1..=stacks[from].len().min(10));
+        let rest = stacks[from].len() - count;
+        let moved = stacks[from].split_off(rest);
+        stacks[to].extend(moved);
+        writeln!(out, "move {count} from {} to {}", from + 1, to + 1)?;
+    }
+    Ok(())
+}
+
+/// A datastream of about `size` characters with both markers near its end.
+fn signal(rng: &mut StdRng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    // Three letters can't contain a start-of-packet marker.
+    for _ in 0..size.saturating_sub(32) {
+        write!(out, "{}", rng.random_range('a'..='c'))?;
+    }
+    let mut marker = ('a'..='z').collect::<Vec<_>>();
+    marker.shuffle(rng);
+    let end = (0..18).map(|_| rng.random_range('a'..='z'));
+    writeln!(
+        out,
+        "{}",
+        marker[..14].iter().copied().chain(end).collect::<String>()
+    )
+}
+
+/// Directory tree of a terminal transcript.
+#[derive(Default)]
+struct Dir {
+    files: Vec<(String, usize)>,
+    dirs: Vec<(String, Dir)>,
+}
+
+impl Dir {
+    /// Builds a random tree holding `files` files.
+    fn random(rng: &mut StdRng, files: usize, depth: usize) -> Dir {
+        let mut dir = Dir::default();
+        let mut names = HashSet::new();
+        let mut unique_name = |rng: &mut StdRng| loop {
+            let name = word(rng);
+            if names.insert(name.clone()) {
+                break name;
+            }
+        };
+
+        let own = if depth >= 8 {
+            files
+        } else {
+            rng.random_range(0..=files.min(4))
+        };
+        for _ in 0..own {
+            let name = unique_name(rng);
+            let ext = ["", ".txt", ".dat", ".log", ".lst"]
+                .choose(rng)
+                .unwrap_or(&"");
+            dir.files
+                .push((format!("{name}{ext}"), rng.random_range(1000..=300_000)));
+        }
+
+        let mut rest = files - own;
+        while rest > 0 {
+            let n = rng.random_range(1..=rest);
+            rest -= n;
+            let child = Dir::random(rng, n, depth + 1);
+            dir.dirs.push((unique_name(rng), child));
+        }
+        if depth < 8 && rng.random_bool(0.1) {
+            dir.dirs.push((unique_name(rng), Dir::default()));
+        }
+        dir
+    }
+
+    /// Writes the commands exploring this directory.
+    fn explore(&self, rng: &mut StdRng, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "$ ls")?;
+        let mut listing = self
+            .dirs
+            .iter()
+            .map(|(name, _)| format!("dir {name}"))
+            .chain(
+                self.files
+                    .iter()
+                    .map(|(name, size)| format!("{size} {name}")),
+            )
+            .collect::<Vec<_>>();
+        listing.shuffle(rng);
+        for line in listing {
+            writeln!(out, "{line}")?;
+        }
+
+        for (name, dir) in &self.dirs {
+            writeln!(out, "$ cd {name}")?;
+            dir.explore(rng, out)?;
+            writeln!(out, "$ cd ..")?;
+        }
+        Ok(())
+    }
+}
+
+/// Transcript exploring a file system with `size` files.
+fn terminal(rng: &mut StdRng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let root = Dir::random(rng, size, 0);
+    writeln!(out, "$ cd /")?;
+    root.explore(rng, out)
+}
+
+/// Square forest with sides of `size` trees.
+fn forest(rng: &mut StdRng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..size.max(1) {
+        let row = (0..size.max(1))
+            .map(|_| rng.random_range('0'..='9'))
+            .collect::<String>();
+        writeln!(out, "{row}")?;
+    }
+    Ok(())
+}
+
+/// `size` moves of the rope's head.
+fn rope_moves(rng: &mut StdRng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..size {
+        let dir = ['U', 'D', 'L', 'R'][rng.random_range(0..4)];
+        writeln!(out, "{dir} {}", rng.random_range(1..=20))?;
+    }
+    Ok(())
+}
+
+/// A program running for at least `size` cycles, and at least long enough
+/// to draw the whole screen.
+fn program(rng: &mut StdRng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut cycles = 0;
+    while cycles < size.max(240) {
+        if rng.random_bool(0.3) {
+            writeln!(out, "noop")?;
+            cycles += 1;
+        } else {
+            writeln!(out, "addx {}", rng.random_range(-15..=15))?;
+            cycles += 2;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy)]
+enum Op {
+    Add(u64),
+    Mul(u64),
+    Square,
+}
+
+struct Monkey {
+    items: Vec<u64>,
+    op: Op,
+    test_div: u64,
+    target_true: usize,
+    target_false: usize,
+}
+
+/// Returns whether the first part's 20 rounds run without worry levels
+/// overflowing.
+fn fits_part_a(monkeys: &[Monkey]) -> bool {
+    let mut items = monkeys
+        .iter()
+        .map(|m| m.items.iter().copied().collect::<VecDeque<_>>())
+        .collect::<Vec<_>>();
+    for _ in 0..20 {
+        for (i, m) in monkeys.iter().enumerate() {
+            while let Some(item) = items[i].pop_front() {
+                let item = match m.op {
+                    Op::Add(n) => item.checked_add(n),
+                    Op::Mul(n) => item.checked_mul(n),
+                    Op::Square => item.checked_mul(item),
+                };
+                let Some(item) = item.map(|w| w / 3) else {
+                    return false;
+                };
+                let target = if item % m.test_div == 0 {
+                    m.target_true
+                } else {
+                    m.target_false
+                };
+                items[target].push_back(item);
+            }
+        }
+    }
+    true
+}
+
+/// Notes on `size` monkeys, between two and eight so that the product of
+/// their distinct prime divisors keeps the second part from overflowing.
+/// Monkeys whose first part overflows are thrown away and generated anew.
+fn monkeys(rng: &mut StdRng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let n = size.clamp(2, 8);
+    let mut attempts = 0;
+    let monkeys = loop {
+        // Multiplying monkeys easily drive the worry levels of the first part
+        // out of range, so give up on them if they keep doing that.
+        attempts += 1;
+        let multiply = attempts <= 100;
+        let mut primes = vec![2, 3, 5, 7, 11, 13, 17, 19, 23];
+        primes.shuffle(rng);
+        let square = rng.random_range(0..n);
+        let monkeys = (0..n)
+            .map(|i| {
+                let target_true = (i + rng.random_range(1..n)) % n;
+                let mut target_false = (i + rng.random_range(1..n)) % n;
+                if n > 2 {
+                    while target_false == target_true {
+                        target_false = (i + rng.random_range(1..n)) % n;
+                    }
+                }
+                Monkey {
+                    items: (0..rng.random_range(1..=8))
+                        .map(|_| rng.random_range(50..=99))
+                        .collect(),
+                    op: match (multiply, i == square, rng.random_bool(0.5)) {
+                        (true, true, _) => Op::Square,
+                        (true, false, false) => Op::Mul(rng.random_range(2..=19)),
+                        _ => Op::Add(rng.random_range(1..=8)),
+                    },
+                    test_div: primes[i],
+                    target_true,
+                    target_false,
+                }
+            })
+            .collect::<Vec<_>>();
+        if fits_part_a(&monkeys) {
+            break monkeys;
+        }
+    };
+
+    for (i, m) in monkeys.iter().enumerate() {
+        if i > 0 {
+            writeln!(out)?;
+        }
+        let items = m.items.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let op = match m.op {
+            Op::Add(n) => format!("old + {n}"),
+            Op::Mul(n) => format!("old * {n}"),
+            Op::Square => "old * old".to_string(),
+        };
+        writeln!(
+            out,
+            "Monkey {i}:\n  Starting items: {}\n  Operation: new = {op}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}",
+            items.join(", "),
+            m.test_div,
+            m.target_true,
+            m.target_false
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::DAYS;
+    use aoc::Part;
+
+    #[test]
+    fn generators_in_order() {
+        assert!(GENERATORS.iter().zip(&DAYS).all(|(g, d)| g.day == d.day));
+        assert!(find(26).is_err());
+    }
+
+    /// Generates the input of `g` into a string.
+    fn generate(g: &Generator, size: Option<usize>, seed: u64) -> String {
+        let mut out = vec![];
+        g.generate(size, seed, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn seeded() {
+        for g in &GENERATORS {
+            assert_eq!(generate(g, Some(20), 7), generate(g, Some(20), 7));
+        }
+        let g = find(1).unwrap();
+        assert_ne!(generate(g, None, 1), generate(g, None, 2));
+    }
+
+    #[test]
+    fn lines_end_with_newlines() {
+        for g in &GENERATORS {
+            let input = generate(g, Some(10), 3);
+            assert!(
+                input.ends_with('\n') && !input.ends_with("\n\n"),
+                "day {}",
+                g.day
+            );
+        }
+        assert_eq!(generate(find(2).unwrap(), Some(3), 0).lines().count(), 3);
+    }
+
+    #[test]
+    fn generated_inputs_are_solvable() {
+        for (g, day) in GENERATORS.iter().zip(&DAYS) {
+            for (seed, size) in [(0, 0), (1, 1), (2, 10), (3, 100)] {
+                let input = generate(g, Some(size), seed);
+                if let Err(e) = day.solve(input.as_bytes(), &Part::ALL) {
+                    panic!("day {} seed {seed} size {size}: {e}\n{input}", g.day);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+#![warn(clippy::pedantic)]
+
+//! Runner dispatching to the solutions of all days.
+
+pub mod alloc;
+pub mod check;
+pub mod days;
+pub mod export;
+pub mod generate;
+pub mod parallel;
+pub mod report;
+pub mod terminal;
+pub mod visualize;
+
+use aoc::answers::Expected;
+use days::Day;
+
+/// Answer that differs from the expected one.
+#[derive(Debug)]
+pub struct Mismatch {
+    pub day: u8,
+    pub expected: Expected,
+    /// Computed answer, or the error that occurred instead.
+    pub actual: String,
+}
+
+/// Solves every entry of the day's `answers.txt` and returns those that
+/// don't match.
+///
+/// # Errors
+///
+/// Fails if the answers file or one of the inputs it refers to can't be read.
+pub fn check_day(day: &Day) -> aoc::Result<Vec<Mismatch>> {
+    let checked = check::check_day(day)?;
+    Ok(checked
+        .into_iter()
+        .filter(|c| !c.passed())
+        .map(|c| Mismatch {
+            day: c.day,
+            expected: c.expected,
+            actual: c.actual.unwrap_or_else(|e| format!("error: {e}")),
+        })
+        .collect())
+}
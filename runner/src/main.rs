@@ -0,0 +1,335 @@
+#![warn(clippy::pedantic)]
+
+use aoc::{InputArgs, Part};
+use aoc_runner::alloc::{self, Counting};
+use aoc_runner::check::{self, History};
+use aoc_runner::days::{self, Day, Solved, DAYS};
+use aoc_runner::terminal::{self, Playback};
+use aoc_runner::{export, generate, parallel, report, visualize};
+use clap::{Args, Parser, Subcommand};
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+#[derive(Parser)]
+#[command(about = "Runs the Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solves the puzzle of a single day, or of all days
+    Run(RunArgs),
+    /// Prints a random puzzle input for a day
+    Generate(GenerateArgs),
+    /// Shows the simulation of a day step by step in the terminal
+    Visualize(VisualizeArgs),
+    /// Writes pictures of a day's state as PPM, PNG or animated GIF
+    Export(ExportArgs),
+    /// Compares the answers with the expected ones in each day's answers.txt
+    Check(CheckArgs),
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+struct RunArgs {
+    /// Day to run, all days if omitted
+    #[arg(short, long)]
+    day: Option<u8>,
+    /// Part to solve, both if omitted
+    #[arg(short, long)]
+    part: Option<Part>,
+    /// Puzzle input, or `-` for stdin [default: input.txt of the day]
+    #[arg(short, long, requires = "day")]
+    input: Option<PathBuf>,
+    /// Use the bundled example inputs instead
+    #[arg(long, conflicts_with = "input")]
+    sample: bool,
+    /// Print a table of parse and per-part durations
+    #[arg(long)]
+    time: bool,
+    /// Print one JSON record `{day, part, answer, elapsed}` per line
+    #[arg(long, conflicts_with = "time")]
+    json: bool,
+    /// Solve all selected days and parts concurrently, reporting failed days
+    /// without stopping the others
+    #[arg(long, conflicts_with = "input")]
+    parallel: bool,
+    /// Print a table of allocation counts, bytes allocated and peak bytes in
+    /// use while parsing and solving each part
+    #[arg(long, conflicts_with_all = ["json", "parallel"])]
+    alloc: bool,
+}
+
+#[derive(Args)]
+struct GenerateArgs {
+    /// Day to generate an input for
+    #[arg(short, long)]
+    day: u8,
+    /// Size of the input, e.g. the number of lines [default: like the real input]
+    #[arg(short, long)]
+    size: Option<usize>,
+    /// Seed of the random number generator
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+}
+
+/// Prints the answers of a day unless only timings were asked for.
+fn print_solved(args: &RunArgs, day: &Day, solved: &Solved) {
+    if args.json {
+        for p in &solved.parts {
+            println!("{}", report::json_record(day.day, p));
+        }
+    } else if !args.time && !args.alloc {
+        if args.day.is_none() {
+            println!("Day {}", day.day);
+        }
+        for p in &solved.parts {
+            aoc::print_answer(p.part, &p.answer);
+        }
+    }
+}
+
+#[derive(Args)]
+struct VisualizeArgs {
+    /// Day to visualize: 5, 9 or 10
+    #[arg(short, long)]
+    day: u8,
+    /// Part whose simulation to show
+    #[arg(short, long, default_value = "a")]
+    part: Part,
+    /// Puzzle input, or `-` for stdin [default: input.txt of the day]
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+    /// Use the bundled example input instead
+    #[arg(long, conflicts_with = "input")]
+    sample: bool,
+    /// Steps per second, between 0.25 and 1000
+    #[arg(long, default_value_t = 10.0)]
+    fps: f64,
+    /// Start paused, to go through the simulation step by step
+    #[arg(long)]
+    paused: bool,
+}
+
+#[derive(Args)]
+struct ExportArgs {
+    /// Day to draw: 8, 9 or 10
+    #[arg(short, long)]
+    day: u8,
+    /// Part whose state to draw
+    #[arg(short, long, default_value = "a")]
+    part: Part,
+    /// Puzzle input, or `-` for stdin [default: input.txt of the day]
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+    /// Use the bundled example input instead
+    #[arg(long, conflicts_with = "input")]
+    sample: bool,
+    /// Image to write: .ppm or .png for the final state, .gif for an
+    /// animation of every step
+    #[arg(short, long)]
+    output: PathBuf,
+    /// Write every step as a numbered .ppm or .png image next to the output
+    #[arg(long)]
+    frames: bool,
+    /// Width and height of a cell in pixels
+    #[arg(long, default_value = "4")]
+    scale: NonZeroUsize,
+    /// Keep only every Nth step of an animation
+    #[arg(long, default_value = "1")]
+    every: NonZeroUsize,
+    /// Milliseconds every frame of a GIF is shown
+    #[arg(long, default_value_t = 50)]
+    delay: u64,
+}
+
+#[derive(Args)]
+struct CheckArgs {
+    /// Day to check, all days if omitted
+    #[arg(short, long)]
+    day: Option<u8>,
+    /// Directory of the answers recorded by earlier checks
+    /// [default: .aoc-check in the workspace]
+    #[arg(long)]
+    state: Option<PathBuf>,
+}
+
+fn run(args: &RunArgs) -> aoc::Result<()> {
+    let days = match args.day {
+        Some(day) => vec![days::find(day)?],
+        None => DAYS.iter().collect(),
+    };
+    let input = InputArgs {
+        path: args.input.clone(),
+        sample: args.sample,
+    };
+    let parts = args.part.map_or(Part::ALL.to_vec(), |p| vec![p]);
+
+    if args.alloc {
+        alloc::enable();
+    }
+
+    let mut results = vec![];
+    let mut failed = 0;
+    let start = Instant::now();
+    if args.parallel {
+        for (day, (_, outcome)) in days.iter().zip(parallel::solve_all(&days, &parts, &input)) {
+            match outcome {
+                Ok(solved) => {
+                    print_solved(args, day, &solved);
+                    results.push((day.day, solved));
+                }
+                Err(e) => {
+                    eprintln!("Day {}: Error: {e}", day.day);
+                    failed += 1;
+                }
+            }
+        }
+    } else {
+        for day in &days {
+            let solved = day.solve(input.open(&day.dir())?, &parts)?;
+            print_solved(args, day, &solved);
+            results.push((day.day, solved));
+        }
+    }
+
+    if args.time {
+        print!("{}", report::format_timings(&results));
+        if args.parallel {
+            println!("wall clock: {:.1?}", start.elapsed());
+        }
+    }
+    if args.alloc {
+        print!("{}", report::format_allocations(&results));
+    }
+    if failed > 0 {
+        return Err(format!("{failed} of {} days failed", days.len()).into());
+    }
+    Ok(())
+}
+
+fn visualize(args: &VisualizeArgs) -> aoc::Result<()> {
+    if args.fps.is_nan() || args.fps <= 0.0 {
+        return Err(format!("Invalid speed {} fps, expected more than 0", args.fps).into());
+    }
+    let visualizer = visualize::find(args.day)?;
+    let input = InputArgs {
+        path: args.input.clone(),
+        sample: args.sample,
+    };
+    let mut sim = visualizer.build(&input.read(&days::find(args.day)?.dir())?, args.part)?;
+
+    if io::stdout().is_terminal() {
+        let playback = Playback {
+            fps: args.fps,
+            paused: args.paused,
+        };
+        terminal::play(sim.as_mut(), playback)?;
+    } else {
+        terminal::print_frames(sim.as_mut(), &mut io::stdout().lock())?;
+    }
+    Ok(())
+}
+
+fn export(args: &ExportArgs) -> aoc::Result<()> {
+    let exporter = export::find(args.day)?;
+    let input = InputArgs {
+        path: args.input.clone(),
+        sample: args.sample,
+    };
+    let options = export::Options {
+        scale: args.scale.get(),
+        every: args.every.get(),
+        delay: Duration::from_millis(args.delay),
+        frames: args.frames,
+    };
+    let input = input.read(&days::find(args.day)?.dir())?;
+    let written = exporter.export(&input, args.part, &args.output, &options)?;
+    if args.frames {
+        println!("Wrote {written} frames");
+    }
+    Ok(())
+}
+
+fn check(args: &CheckArgs) -> aoc::Result<()> {
+    let days = match args.day {
+        Some(day) => vec![days::find(day)?],
+        None => DAYS.iter().collect(),
+    };
+    let history = History::new(args.state.clone().unwrap_or_else(History::default_dir));
+
+    let (mut total, mut failed, mut changed, mut broken) = (0, 0, 0, 0);
+    for day in days {
+        let checked = match check::check_day(day) {
+            Ok(checked) => checked,
+            Err(e) => {
+                println!("day {:02}: Error: {e}", day.day);
+                broken += 1;
+                continue;
+            }
+        };
+        let previous = history.load(day.day)?;
+        for c in &checked {
+            let name = format!(
+                "day {:02} {} {}",
+                c.day,
+                c.expected.input,
+                c.expected.part.to_string().to_lowercase()
+            );
+            total += 1;
+            match &c.actual {
+                _ if c.passed() => println!("{name}: ok"),
+                Ok(actual) => {
+                    failed += 1;
+                    println!("{name}: FAIL");
+                    print!(
+                        "{}",
+                        check::diff(["expected", "actual"], &c.expected.answer, actual)
+                    );
+                }
+                Err(e) => {
+                    failed += 1;
+                    println!("{name}: FAIL, Error: {e}");
+                }
+            }
+            if let (Some(before), Ok(now)) = (check::changed(&previous, c), &c.actual) {
+                changed += 1;
+                println!("{name}: changed since the last check");
+                print!("{}", check::diff(["last check", "now"], before, now));
+            }
+        }
+        history.record(day.day, &previous, &checked)?;
+    }
+
+    println!(
+        "{} of {total} answers match, {changed} changed since the last check",
+        total - failed
+    );
+    match (failed, broken) {
+        (0, 0) => Ok(()),
+        (_, 0) => Err(format!("{failed} of {total} answers don't match").into()),
+        _ => Err(format!("{failed} answers don't match, {broken} days couldn't be checked").into()),
+    }
+}
+
+fn main() -> ExitCode {
+    aoc::exit(match Cli::parse().command {
+        Command::Run(args) => run(&args),
+        Command::Generate(args) => generate::find(args.day).and_then(|g| {
+            let mut out = BufWriter::new(io::stdout().lock());
+            g.generate(args.size, args.seed, &mut out)?;
+            Ok(out.flush()?)
+        }),
+        Command::Visualize(args) => visualize(&args),
+        Command::Export(args) => export(&args),
+        Command::Check(args) => check(&args),
+    })
+}
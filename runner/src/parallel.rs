@@ -0,0 +1,109 @@
+//! Solving many days at once, one thread per day and part.
+
+use crate::alloc::Allocations;
+use crate::days::{Day, Solved};
+use aoc::{InputArgs, Part};
+use std::thread;
+use std::time::Duration;
+
+/// Outcome of one day: its results, or the message of the first error or
+/// panic of one of its parts.
+pub type Outcome = Result<Solved, String>;
+
+/// Solves one part of `day` on its own input, turning errors and panics into
+/// messages so they can leave the thread.
+fn solve_part(day: &Day, part: Part, input: &InputArgs) -> Outcome {
+    let reader = input.open(&day.dir()).map_err(|e| e.to_string())?;
+    day.solve(reader, &[part]).map_err(|e| e.to_string())
+}
+
+/// Joins the results of the parts of a day into one, failing with the first
+/// failed part.
+///
+/// Every part parsed its own copy of the input concurrently, so the parse
+/// time is that of the slowest copy.
+/// Allocations are left out, the counts of concurrent threads can't be
+/// told apart.
+fn merge(parts: Vec<Outcome>) -> Outcome {
+    let mut merged = Solved {
+        parse_time: Duration::ZERO,
+        parse_allocations: Allocations::default(),
+        solved_while_parsing: false,
+        parts: vec![],
+    };
+    for solved in parts {
+        let solved = solved?;
+        merged.parse_time = merged.parse_time.max(solved.parse_time);
+        merged.solved_while_parsing = solved.solved_while_parsing;
+        merged.parts.extend(solved.parts);
+    }
+    Ok(merged)
+}
+
+/// Solves `parts` of every day in `days` concurrently, each part in its own
+/// thread parsing its own copy of the input.
+///
+/// Returns the outcome of every day in the order of `days`. A failing or
+/// panicking day doesn't affect the others.
+#[must_use]
+pub fn solve_all(days: &[&Day], parts: &[Part], input: &InputArgs) -> Vec<(u8, Outcome)> {
+    thread::scope(|s| {
+        let handles = days
+            .iter()
+            .map(|&day| {
+                let parts = parts
+                    .iter()
+                    .map(|&part| s.spawn(move || solve_part(day, part, input)))
+                    .collect::<Vec<_>>();
+                (day.day, parts)
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|(day, parts)| {
+                let parts = parts
+                    .into_iter()
+                    .map(|h| h.join().unwrap_or_else(|_| Err("panicked".to_string())))
+                    .collect();
+                (day, merge(parts))
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::{self, DAYS};
+
+    #[test]
+    fn results_in_day_order() {
+        let days = DAYS.iter().rev().collect::<Vec<_>>();
+        let input = InputArgs {
+            path: None,
+            sample: true,
+        };
+        let outcomes = solve_all(&days, &Part::ALL, &input);
+
+        let order = outcomes.iter().map(|(day, _)| *day).collect::<Vec<_>>();
+        assert_eq!(order, days.iter().map(|d| d.day).collect::<Vec<_>>());
+        for (day, outcome) in outcomes {
+            let parts = outcome.unwrap().parts;
+            let parts = parts.iter().map(|p| p.part).collect::<Vec<_>>();
+            assert_eq!(parts, Part::ALL, "day {day}");
+        }
+    }
+
+    #[test]
+    fn failures_stay_in_their_day() {
+        let days = [days::find(1).unwrap(), days::find(2).unwrap()];
+        let input = InputArgs {
+            path: Some(days[0].dir().join("sample.txt")),
+            sample: false,
+        };
+        let outcomes = solve_all(&days, &[Part::A], &input);
+        assert!(outcomes[0].1.is_ok());
+        assert!(outcomes[1].1.as_ref().unwrap_err().contains("line 1"));
+    }
+}
@@ -0,0 +1,270 @@
+use crate::alloc::{format_bytes, Allocations};
+use crate::days::{PartResult, Solved};
+use aoc::Answer;
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Shown instead of the numbers of parts solved while parsing.
+const IN_PARSE: &str = "in parse";
+
+/// Shortens multi-line answers so they fit in a table cell.
+fn short_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Screen(s) => format!("<{}x{} screen>", s.width(), s.rows().count()),
+        a => a.to_string(),
+    }
+}
+
+/// Formats the per-part durations of the given days as a table.
+///
+/// Parts of days solved while parsing show `in parse` instead, their time
+/// is that of the parse.
+#[must_use]
+pub fn format_timings(results: &[(u8, Solved)]) -> String {
+    let mut table = format!(
+        "{:>3}  {:<5}  {:<20}  {:>10}\n",
+        "day", "part", "answer", "time"
+    );
+    let mut total = Duration::ZERO;
+
+    for (day, solved) in results {
+        writeln!(
+            table,
+            "{day:>3}  {:<5}  {:<20}  {:>10}",
+            "parse",
+            "",
+            format!("{:.1?}", solved.parse_time)
+        )
+        .unwrap();
+        total += solved.parse_time;
+
+        for p in &solved.parts {
+            let time = if solved.solved_while_parsing {
+                IN_PARSE.to_string()
+            } else {
+                format!("{:.1?}", p.elapsed)
+            };
+            writeln!(
+                table,
+                "{day:>3}  {:<5}  {:<20}  {time:>10}",
+                p.part.to_string(),
+                short_answer(&p.answer),
+            )
+            .unwrap();
+            total += p.elapsed;
+        }
+    }
+
+    writeln!(
+        table,
+        "{:>3}  {:<5}  {:<20}  {:>10}",
+        "",
+        "total",
+        "",
+        format!("{total:.1?}")
+    )
+    .unwrap();
+    table
+}
+
+/// Formats the allocation counts, bytes allocated and peak bytes in use of
+/// the parsing and every part of the given days as a table.
+///
+/// Parts of days solved while parsing show `in parse` instead, their
+/// allocations are counted in the parse.
+#[must_use]
+pub fn format_allocations(results: &[(u8, Solved)]) -> String {
+    let mut table = format!(
+        "{:>3}  {:<5}  {:>12}  {:>12}  {:>12}\n",
+        "day", "part", "allocations", "allocated", "peak"
+    );
+    let row = |table: &mut String, day: &str, part: &str, a: &Allocations| {
+        writeln!(
+            table,
+            "{day:>3}  {part:<5}  {:>12}  {:>12}  {:>12}",
+            a.count,
+            format_bytes(a.bytes),
+            format_bytes(a.peak)
+        )
+        .unwrap();
+    };
+    let mut total = Allocations::default();
+    let mut add = |a: &Allocations| {
+        total.count += a.count;
+        total.bytes += a.bytes;
+        total.peak = total.peak.max(a.peak);
+    };
+
+    for (day, solved) in results {
+        let day = day.to_string();
+        row(&mut table, &day, "parse", &solved.parse_allocations);
+        add(&solved.parse_allocations);
+        for p in &solved.parts {
+            if solved.solved_while_parsing {
+                writeln!(table, "{day:>3}  {:<5}  {IN_PARSE:>12}", p.part.to_string()).unwrap();
+            } else {
+                row(&mut table, &day, &p.part.to_string(), &p.allocations);
+            }
+            add(&p.allocations);
+        }
+    }
+
+    row(&mut table, "", "total", &total);
+    table
+}
+
+/// Converts an answer to JSON: numbers and strings as such, screens as an
+/// object with both the raw pixels and the rendered rows. Numbers beyond the
+/// 64-bit range of JSON numbers are written as strings.
+fn answer_json(answer: &Answer) -> Value {
+    match answer {
+        Answer::Int(n) => match (i64::try_from(*n), u64::try_from(*n)) {
+            (Ok(n), _) => json!(n),
+            (_, Ok(n)) => json!(n),
+            _ => json!(n.to_string()),
+        },
+        Answer::Text(s) => json!(s),
+        Answer::Screen(s) => json!({
+            "width": s.width(),
+            "height": s.rows().count(),
+            "pixels": s.pixels(),
+            "rows": s.rows().collect::<Vec<_>>(),
+        }),
+    }
+}
+
+/// Formats one part as a JSON record `{day, part, answer, elapsed}`, with
+/// `elapsed` in seconds.
+#[must_use]
+pub fn json_record(day: u8, result: &PartResult) -> String {
+    json!({
+        "day": day,
+        "part": result.part.to_string(),
+        "answer": answer_json(&result.answer),
+        "elapsed": result.elapsed.as_secs_f64(),
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::{Part, Screen};
+
+    fn solved() -> Solved {
+        Solved {
+            parse_time: Duration::from_micros(3),
+            parse_allocations: Allocations {
+                count: 1,
+                bytes: 100,
+                peak: 4096,
+            },
+            solved_while_parsing: false,
+            parts: vec![
+                PartResult {
+                    part: Part::A,
+                    answer: Answer::Int(42),
+                    elapsed: Duration::from_millis(2),
+                    allocations: Allocations {
+                        count: 3,
+                        bytes: 2048,
+                        peak: 1024,
+                    },
+                },
+                PartResult {
+                    part: Part::B,
+                    answer: Screen::new(2, vec![true; 4]).into(),
+                    elapsed: Duration::from_millis(1),
+                    allocations: Allocations::default(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn timings_table() {
+        assert_eq!(
+            format_timings(&[(10, solved())]),
+            "\
+day  part   answer                      time
+ 10  parse                             3.0µs
+ 10  A      42                         2.0ms
+ 10  B      <2x2 screen>               1.0ms
+     total                             3.0ms
+"
+        );
+    }
+
+    #[test]
+    fn allocations_table() {
+        assert_eq!(
+            format_allocations(&[(10, solved())]),
+            "\
+day  part    allocations     allocated          peak
+ 10  parse             1         100 B       4.0 KiB
+ 10  A                 3       2.0 KiB       1.0 KiB
+ 10  B                 0           0 B           0 B
+     total             4       2.1 KiB       4.0 KiB
+"
+        );
+    }
+
+    #[test]
+    fn solved_while_parsing() {
+        let solved = Solved {
+            solved_while_parsing: true,
+            ..solved()
+        };
+        let results = [(2, solved)];
+        assert!(
+            format_timings(&results).contains("\n  2  A      42                      in parse\n")
+        );
+        assert!(format_allocations(&results).contains("\n  2  A          in parse\n"));
+    }
+
+    #[test]
+    fn json_records() {
+        let int = PartResult {
+            part: Part::A,
+            answer: Answer::Int(14_399_640_002),
+            elapsed: Duration::from_millis(5),
+            allocations: Allocations::default(),
+        };
+        assert_eq!(
+            json_record(11, &int),
+            r#"{"answer":14399640002,"day":11,"elapsed":0.005,"part":"A"}"#
+        );
+
+        let screen = PartResult {
+            part: Part::B,
+            answer: Screen::new(2, vec![true, false, false, true]).into(),
+            elapsed: Duration::ZERO,
+            allocations: Allocations::default(),
+        };
+        assert_eq!(
+            json_record(10, &screen),
+            r##"{"answer":{"height":2,"pixels":[true,false,false,true],"rows":["#.",".#"],"width":2},"day":10,"elapsed":0.0,"part":"B"}"##
+        );
+    }
+
+    #[test]
+    fn json_big_numbers() {
+        let record = |n: i128| {
+            let result = PartResult {
+                part: Part::A,
+                answer: Answer::Int(n),
+                elapsed: Duration::ZERO,
+                allocations: Allocations::default(),
+            };
+            json_record(1, &result)
+        };
+        let max = u64::MAX;
+        assert!(record(max.into()).starts_with(&format!(r#"{{"answer":{max},"#)));
+        assert!(record(i64::MIN.into()).starts_with(&format!(r#"{{"answer":{},"#, i64::MIN)));
+        let big = i128::from(max) + 1;
+        assert!(record(big).starts_with(&format!(r#"{{"answer":"{big}","#)));
+        let small = i128::from(i64::MIN) - 1;
+        assert!(record(small).starts_with(&format!(r#"{{"answer":"{small}","#)));
+    }
+}
@@ -0,0 +1,224 @@
+//! Terminal player for [`Visualize`] simulations.
+
+use aoc::visual::{Frame, Visualize};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::terminal::{self, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{cursor, execute, queue};
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+const HELP: &str = "space: pause  n/→: step  +/-: speed  q: quit";
+
+/// Slowest and fastest speed of the player, in steps per second.
+const FPS_RANGE: (f64, f64) = (0.25, 1000.0);
+
+/// Playback settings of the terminal player.
+#[derive(Debug, Clone, Copy)]
+pub struct Playback {
+    /// Steps per second while playing, kept between 0.25 and 1000.
+    pub fps: f64,
+    /// Whether to start paused, stepping only on request.
+    pub paused: bool,
+}
+
+/// Returns the time between steps at `fps` steps per second, at the slowest
+/// speed if `fps` isn't a number.
+fn interval(fps: f64) -> Duration {
+    let slowest = Duration::from_secs_f64(1.0 / FPS_RANGE.0);
+    Duration::try_from_secs_f64(1.0 / fps.clamp(FPS_RANGE.0, FPS_RANGE.1)).unwrap_or(slowest)
+}
+
+/// Puts the terminal into raw mode on an alternate screen for as long as it
+/// lives.
+struct RawScreen;
+
+impl RawScreen {
+    fn enter() -> io::Result<RawScreen> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, cursor::Hide)?;
+        Ok(RawScreen)
+    }
+}
+
+impl Drop for RawScreen {
+    fn drop(&mut self) {
+        // Restoring the terminal is best effort, there is nothing left to
+        // report errors to.
+        let _ = execute!(io::stdout(), cursor::Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// What the user asked for with a key press.
+enum Command {
+    Quit,
+    TogglePause,
+    Step,
+    Faster,
+    Slower,
+}
+
+/// Waits up to `timeout`, or forever if `None`, for a key press.
+fn next_command(timeout: Option<Duration>) -> io::Result<Option<Command>> {
+    let deadline = timeout.map(|t| Instant::now() + t);
+    loop {
+        if let Some(deadline) = deadline {
+            if !event::poll(deadline.saturating_duration_since(Instant::now()))? {
+                return Ok(None);
+            }
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let command = match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Command::Quit,
+            KeyCode::Char('q') | KeyCode::Esc => Command::Quit,
+            KeyCode::Char(' ') => Command::TogglePause,
+            KeyCode::Char('n') | KeyCode::Right => Command::Step,
+            KeyCode::Char('+' | '=') => Command::Faster,
+            KeyCode::Char('-') => Command::Slower,
+            _ => continue,
+        };
+        return Ok(Some(command));
+    }
+}
+
+/// Draws the caption, as much of the frame as fits and the status line.
+fn draw(out: &mut impl Write, frame: &Frame, status: &str) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    let (width, rows) = (usize::from(width), height.saturating_sub(2));
+    let fit = |line: &str| line.chars().take(width).collect::<String>();
+
+    queue!(
+        out,
+        terminal::Clear(ClearType::All),
+        cursor::MoveTo(0, 0),
+        Print(fit(&frame.caption))
+    )?;
+    for (y, line) in (1..).zip(frame.viewport(width, rows.into())) {
+        queue!(out, cursor::MoveTo(0, y), Print(line))?;
+    }
+    queue!(
+        out,
+        cursor::MoveTo(0, height.saturating_sub(1)),
+        Print(fit(status))
+    )?;
+    out.flush()
+}
+
+/// Plays `sim` in the terminal until it is quit, starting with the initial
+/// frame.
+///
+/// Frames larger than the terminal are cropped around their focus. Once the
+/// simulation is finished its last frame stays on screen.
+///
+/// # Errors
+///
+/// Fails if the terminal can't be controlled.
+pub fn play(sim: &mut dyn Visualize, mut playback: Playback) -> io::Result<()> {
+    if !playback.fps.is_nan() {
+        playback.fps = playback.fps.clamp(FPS_RANGE.0, FPS_RANGE.1);
+    }
+    let _screen = RawScreen::enter()?;
+    let mut out = io::stdout().lock();
+    let mut steps = 0;
+    let mut finished = false;
+    let mut next_step = Instant::now();
+
+    loop {
+        let state = match (finished, playback.paused) {
+            (true, _) => "finished",
+            (false, true) => "paused",
+            (false, false) => "playing",
+        };
+        let status = format!("step {steps}  {} fps  {state}  |  {HELP}", playback.fps);
+        draw(&mut out, &sim.frame(), &status)?;
+
+        let interval = interval(playback.fps);
+        let timeout = (!finished && !playback.paused)
+            .then(|| next_step.saturating_duration_since(Instant::now()));
+        let step = match next_command(timeout)? {
+            Some(Command::Quit) => return Ok(()),
+            Some(Command::TogglePause) => {
+                playback.paused = !playback.paused;
+                next_step = Instant::now() + interval;
+                false
+            }
+            Some(Command::Step) => playback.paused,
+            Some(Command::Faster) => {
+                playback.fps = (playback.fps * 2.0).min(FPS_RANGE.1);
+                false
+            }
+            Some(Command::Slower) => {
+                playback.fps = (playback.fps / 2.0).max(FPS_RANGE.0);
+                false
+            }
+            None => {
+                next_step = Instant::now() + interval;
+                true
+            }
+        };
+        if step && !finished {
+            if sim.step() {
+                steps += 1;
+            } else {
+                finished = true;
+            }
+        }
+    }
+}
+
+/// Writes every frame of `sim` to `out`, separated by blank lines, for when
+/// there is no terminal to play it in.
+///
+/// # Errors
+///
+/// Fails if `out` can't be written to.
+pub fn print_frames(sim: &mut dyn Visualize, out: &mut impl Write) -> io::Result<()> {
+    loop {
+        let frame = sim.frame();
+        writeln!(out, "{}\n{}", frame.caption, frame.cells)?;
+        if !sim.step() {
+            return Ok(());
+        }
+        writeln!(out)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts down to zero, one step at a time.
+    struct Countdown(u8);
+
+    impl Visualize for Countdown {
+        fn frame(&self) -> Frame {
+            Frame::from_text(format!("{} left", self.0), &"#".repeat(self.0.into()))
+        }
+
+        fn step(&mut self) -> bool {
+            self.0.checked_sub(1).map(|n| self.0 = n).is_some()
+        }
+    }
+
+    #[test]
+    fn step_intervals() {
+        assert_eq!(interval(10.0), Duration::from_millis(100));
+        assert_eq!(interval(1e-30), Duration::from_secs(4));
+        assert_eq!(interval(f64::INFINITY), Duration::from_millis(1));
+        assert_eq!(interval(f64::NAN), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn printed_frames() {
+        let mut out = vec![];
+        print_frames(&mut Countdown(2), &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text, "2 left\n##\n\n1 left\n#\n\n0 left\n\n");
+    }
+}
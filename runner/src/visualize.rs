@@ -0,0 +1,109 @@
+//! Step-by-step simulations of the days that have one.
+//!
+//! Each visualizer parses a day's input and sets up its [`Visualize`]
+//! implementation, which the [`terminal`](crate::terminal) player then shows
+//! frame by frame.
+
+use aoc::visual::Visualize;
+use aoc::Part;
+
+/// Sets up the simulation of one day.
+pub struct Visualizer {
+    pub day: u8,
+    build: fn(&str, Part) -> aoc::Result<Box<dyn Visualize>>,
+}
+
+impl Visualizer {
+    /// Parses `input` and sets up the simulation `part` of the puzzle asks
+    /// for.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the input can't be parsed.
+    pub fn build(&self, input: &str, part: Part) -> aoc::Result<Box<dyn Visualize>> {
+        (self.build)(input, part)
+    }
+}
+
+/// Day 5: the crane moving crates one move per step, one crate at a time in
+/// part A and several at once in part B.
+fn crane(input: &str, part: Part) -> aoc::Result<Box<dyn Visualize>> {
+    let (stacks, moves) = day05::parse_input(input)?;
+    Ok(Box::new(day05::Crane::new(stacks, moves, part == Part::B)))
+}
+
+/// Day 9: the rope following its head square by square, with 2 knots in
+/// part A and 10 in part B.
+fn rope(input: &str, part: Part) -> aoc::Result<Box<dyn Visualize>> {
+    let moves = day09::parse_moves(input)?;
+    let knots = match part {
+        Part::A => 2,
+        Part::B => 10,
+    };
+    Ok(Box::new(day09::RopeMotion::new(moves, knots)))
+}
+
+/// Day 10: the CRT drawing one pixel per cycle, the same for both parts.
+fn device(input: &str, _: Part) -> aoc::Result<Box<dyn Visualize>> {
+    Ok(Box::new(day10::Device::new(day10::parse_instructions(
+        input,
+    )?)))
+}
+
+pub const VISUALIZERS: [Visualizer; 3] = [
+    Visualizer {
+        day: 5,
+        build: crane,
+    },
+    Visualizer {
+        day: 9,
+        build: rope,
+    },
+    Visualizer {
+        day: 10,
+        build: device,
+    },
+];
+
+/// Looks up the visualizer of `day`.
+///
+/// # Errors
+///
+/// Fails if `day` has no visualization.
+pub fn find(day: u8) -> aoc::Result<&'static Visualizer> {
+    VISUALIZERS.iter().find(|v| v.day == day).ok_or_else(|| {
+        let days = VISUALIZERS.map(|v| v.day.to_string()).join(", ");
+        format!("No visualization for day {day}, only for days {days}").into()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn samples_run_to_the_end() {
+        for v in &VISUALIZERS {
+            let input =
+                aoc::read_input(days::find(v.day).unwrap().dir().join("sample.txt")).unwrap();
+            for part in Part::ALL {
+                let mut sim = v.build(&input, part).unwrap();
+                let first = sim.frame();
+                let mut steps = 0;
+                while sim.step() {
+                    steps += 1;
+                }
+                assert!(steps > 0, "day {} {part}", v.day);
+                assert_ne!(sim.frame(), first, "day {} {part}", v.day);
+                assert!(!sim.step(), "day {} {part}", v.day);
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_day() {
+        assert!(find(9).is_ok());
+        assert!(find(1).is_err());
+    }
+}
@@ -0,0 +1,66 @@
+use aoc::Part;
+use aoc_runner::check_day;
+use aoc_runner::days::DAYS;
+use std::fmt::Write;
+use std::io::BufRead;
+
+#[test]
+fn all_days_match_expected_answers() {
+    let mut report = String::new();
+
+    for day in &DAYS {
+        match check_day(day) {
+            Ok(mismatches) => {
+                for m in mismatches {
+                    writeln!(
+                        report,
+                        "day {:02} {} {}:\n  expected: {}\n  actual:   {}",
+                        m.day, m.expected.input, m.expected.part, m.expected.answer, m.actual
+                    )
+                    .unwrap();
+                }
+            }
+            Err(e) => writeln!(report, "day {:02}: {e}", day.day).unwrap(),
+        }
+    }
+
+    assert!(report.is_empty(), "answers changed:\n{report}");
+}
+
+#[test]
+fn windows_edited_samples_give_the_same_answers() {
+    let dir = std::env::temp_dir().join(format!("aoc-crlf-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    for day in &DAYS {
+        let sample = day.dir().join("sample.txt");
+        let clean = std::fs::read_to_string(&sample).unwrap();
+        let mangled = format!("\u{feff}{}\r\n \r\n\r\n", clean.replace('\n', " \r\n"));
+        let path = dir.join(format!("day{:02}.txt", day.day));
+        std::fs::write(&path, mangled).unwrap();
+
+        let answers = |input: &mut dyn BufRead| {
+            day.solve(input, &Part::ALL)
+                .unwrap()
+                .parts
+                .into_iter()
+                .map(|p| p.answer.to_string())
+                .collect::<Vec<_>>()
+        };
+        let expected = answers(&mut aoc::open_input(&sample).unwrap());
+        assert_eq!(
+            answers(&mut aoc::read_input(&path).unwrap().as_bytes()),
+            expected,
+            "day {:02} read",
+            day.day
+        );
+        assert_eq!(
+            answers(&mut aoc::open_input(&path).unwrap()),
+            expected,
+            "day {:02} streamed",
+            day.day
+        );
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}